@@ -1,39 +1,161 @@
+#include <errno.h>
 #include <stdio.h>
+#include <stdlib.h>
 #include "tenthash.h"
 
-int main(void) {
-    const static uint8_t inputs[][64] = {
-        {},
-        {0},
-        "0123456789",
-        "abcdefghijklmnopqrstuvwxyz",
-        "This string is exactly 32 bytes.",
-        "The quick brown fox jumps over the lazy dog.",
-    };
-    const static int inputs_len[] = {0, 1, 10, 26, 32, 44};
-    const static uint8_t digests[][24] = {
-        {0x68, 0xc8, 0x21, 0x3b, 0x7a, 0x76, 0xb8, 0xed, 0x26, 0x7d, 0xdd, 0xb3, 0xd8, 0x71, 0x7b, 0xb3, 0xb6, 0xe7, 0xcc, 0x0a},
-        {0x3c, 0xf6, 0x83, 0x3c, 0xca, 0x9c, 0x4d, 0x5e, 0x21, 0x13, 0x18, 0x57, 0x7b, 0xab, 0x74, 0xbf, 0x12, 0xa4, 0xf0, 0x90},
-        {0xa7, 0xd3, 0x24, 0xbd, 0xe0, 0xbf, 0x6c, 0xe3, 0x42, 0x77, 0x01, 0x62, 0x8f, 0x0f, 0x8f, 0xc3, 0x29, 0xc2, 0xa1, 0x16},
-        {0xf1, 0xbe, 0x4b, 0xe1, 0xa0, 0xf9, 0xea, 0xe6, 0x50, 0x0f, 0xb2, 0xf6, 0xb6, 0x4f, 0x3d, 0xaa, 0x39, 0x90, 0xac, 0x1a},
-        {0xf7, 0xc5, 0xe4, 0x76, 0x3d, 0x89, 0xbd, 0xdc, 0xe3, 0x3e, 0x97, 0x71, 0x2b, 0x71, 0x2d, 0x86, 0x9a, 0xab, 0xcf, 0xe9},
-        {0xde, 0x77, 0xf1, 0xc1, 0x34, 0x22, 0x8b, 0xe1, 0xb5, 0xb2, 0x5c, 0x94, 0x1d, 0x51, 0x02, 0xf8, 0x7f, 0x3e, 0x6d, 0x39},
-    };
+// Runs TentHash over the test vector corpus.  See test_vectors/README.md for
+// the corpus format.
 
+#define DEFAULT_CORPUS_PATH "../../test_vectors/corpus.txt"
+#define MAX_LINE_LEN 1024
+
+// Parses a hex digit, returning -1 if it isn't one.
+int hex_digit(char c) {
+    if (c >= '0' && c <= '9') {
+        return c - '0';
+    } else if (c >= 'a' && c <= 'f') {
+        return c - 'a' + 10;
+    }
+    return -1;
+}
+
+// Parses `len` bytes of hex into `out`, returning 0 on failure.
+int parse_hex(const char *hex, uint8_t *out, size_t len) {
+    for (size_t i = 0; i < len; i++) {
+        int hi = hex_digit(hex[i * 2]);
+        int lo = hex_digit(hex[i * 2 + 1]);
+        if (hi < 0 || lo < 0) {
+            return 0;
+        }
+        out[i] = (uint8_t)(hi << 4 | lo);
+    }
+    return 1;
+}
+
+// Parses a decimal message length into `out`, returning 0 on failure.
+int parse_length(const char *text, size_t *out) {
+    // strtoull() also accepts leading whitespace and signs, so check that
+    // the length starts with a digit.
+    if (*text < '0' || *text > '9') {
+        return 0;
+    }
+    char *end;
+    errno = 0;
+    unsigned long long len = strtoull(text, &end, 10);
+    // The message is allocated with an extra byte, so leave room for that.
+    if (errno != 0 || *end != '\0' || len >= SIZE_MAX) {
+        return 0;
+    }
+    *out = (size_t)len;
+    return 1;
+}
+
+int main(int argc, char **argv) {
+    const char *path = argc > 1 ? argv[1] : DEFAULT_CORPUS_PATH;
+    FILE *file = fopen(path, "r");
+    if (file == NULL) {
+        printf("Couldn't open test vector corpus \"%s\".\n", path);
+        return 1;
+    }
+
+    // Room for the line, its terminator, and the null byte.
+    static char line[MAX_LINE_LEN + 2];
+    int status = 1;
+    int line_number = 0;
+    int have_version = 0;
+    int vectors = 0;
     int failures = 0;
-    for (int i = 0; i < 5; i++) {
-        Digest output = hash(inputs[i], inputs_len[i]);
-
-        for (int j = 0; j < 20; j++) {
-            if (output.bytes[j] != digests[i][j]) {
-                printf("Failed test vector %d.\n", i + 1);
-                failures++;
-                break;
+
+    while (fgets(line, sizeof(line), file) != NULL) {
+        line_number++;
+
+        size_t line_len = strlen(line);
+        if (line_len > 0 && line[line_len - 1] == '\n') {
+            line[--line_len] = '\0';
+        } else if (!feof(file)) {
+            printf("Line %d of the corpus is too long.\n", line_number);
+            goto done;
+        }
+        if (line_len == 0 || line[0] == '#') {
+            continue;
+        }
+
+        if (!have_version) {
+            if (strcmp(line, "tenthash-test-vectors 1") != 0) {
+                printf("Unsupported corpus format: \"%s\".\n", line);
+                goto done;
+            }
+            have_version = 1;
+            continue;
+        }
+
+        // Parse the test vector.
+        uint8_t expected[TENT_DIGEST_SIZE];
+        if (line_len < TENT_DIGEST_SIZE * 2 + 1
+            || line[TENT_DIGEST_SIZE * 2] != ' '
+            || !parse_hex(line, expected, TENT_DIGEST_SIZE)) {
+            printf("Invalid test vector on line %d.\n", line_number);
+            goto done;
+        }
+        const char *message = line + TENT_DIGEST_SIZE * 2 + 1;
+
+        uint8_t *data;
+        size_t data_len;
+        if (strncmp(message, "hex:", 4) == 0) {
+            const char *hex = message + 4;
+            if (strlen(hex) % 2 != 0) {
+                printf("Invalid hex message on line %d.\n", line_number);
+                goto done;
             }
+            data_len = strlen(hex) / 2;
+            data = malloc(data_len + 1);
+            if (data == NULL) {
+                printf("Out of memory on line %d.\n", line_number);
+                goto done;
+            }
+            if (!parse_hex(hex, data, data_len)) {
+                printf("Invalid hex message on line %d.\n", line_number);
+                free(data);
+                goto done;
+            }
+        } else if (strncmp(message, "pattern:", 8) == 0) {
+            if (!parse_length(message + 8, &data_len)) {
+                printf("Invalid pattern length on line %d.\n", line_number);
+                goto done;
+            }
+            data = malloc(data_len + 1);
+            if (data == NULL) {
+                printf("Out of memory on line %d.\n", line_number);
+                goto done;
+            }
+            for (size_t i = 0; i < data_len; i++) {
+                data[i] = i % 251;
+            }
+        } else {
+            printf("Unknown message on line %d.\n", line_number);
+            goto done;
+        }
+
+        // Check the digest.
+        Digest output = hash(data, data_len);
+        if (memcmp(output.bytes, expected, TENT_DIGEST_SIZE) != 0) {
+            printf("Failed test vector on line %d.\n", line_number);
+            failures++;
         }
+        vectors++;
+        free(data);
     }
 
-    if (failures == 0) {
-        printf("All test vectors passed.\n");
+    if (vectors == 0) {
+        printf("No test vectors found.\n");
+    } else if (failures > 0) {
+        printf("%d of %d test vectors failed.\n", failures, vectors);
+    } else {
+        printf("All %d test vectors passed.\n", vectors);
+        status = 0;
     }
+
+done:
+    fclose(file);
+    return status;
 }
@@ -3,148 +3,445 @@
 //! let us do statistical collision tests at an appreciable portion of the
 //! function's output size, which is infeasible on the full-size mix function.
 //! But it makes other analysis run faster as well.
+//!
+//! The state width, rotation constants, round count, and which tests to run
+//! are all configurable from the command line, and the results can be output
+//! as JSON for running sweeps from scripts.  Run with `--help` for details.
 
+mod mixer;
 mod stats;
 
-use stats::{bit_combinations, compute_stats, generate_random};
+use serde::Serialize;
 
-fn main() {
-    for pattern in PATTERNS {
-        println!("\n{}:", pattern.name);
-        if pattern.collision_log_population > 0 {
-            collision_test(
-                pattern.collision_log_population,
-                1 << pattern.collision_log_population,
-                pattern.gen_function,
-            );
-        }
-        let chart = compute_stats(
-            pattern.gen_function,
-            |a, b| {
-                *b = *a;
-                mix(b);
-            },
-            pattern.avalanche_rounds,
-        );
-        chart.print_report();
-    }
-}
+use mixer::{Mixer, DEFAULT_ROTATIONS_32};
+use stats::{bit_combinations, compute_stats, generate_random, width_mask, Bits, Report};
 
-type State = u32;
-
-/// Same construction as TentHash's mixing function, just with a smaller state.
-fn mix(state: &mut State) {
-    const ROTS: &[[u32; 2]] = &[
-        // The rounds above the line have been tuned to give similar diffusion
-        // (relative to total bits) as TentHash's full-size mixing function.
-        // Uncommenting the rounds below the line will give full diffusion.
-        [2, 5],
-        [7, 4],
-        [1, 2],
-        [2, 5],
-        [7, 4],
-        [1, 2],
-        //----------
-        // [2, 5],
-        // [7, 4],
-        // [1, 2],
-    ];
-
-    let mut bytes: [u8; 4] = unsafe { std::mem::transmute(*state) };
-    for rot in ROTS {
-        bytes[0] = bytes[0].wrapping_add(bytes[2]);
-        bytes[1] = bytes[1].wrapping_add(bytes[3]);
-        bytes[2] = bytes[2].rotate_left(rot[0]) ^ bytes[0];
-        bytes[3] = bytes[3].rotate_left(rot[1]) ^ bytes[1];
-        bytes.swap(0, 1);
-    }
-    *state = unsafe { std::mem::transmute(bytes) };
-}
+const USAGE: &str = "\
+Usage: tiny_mixer [OPTION]...
+Run statistical tests on a reduced-size version of TentHash's mixer.
 
-struct BitPattern<'a> {
-    name: &'a str,
-    gen_function: &'a dyn Fn(usize) -> State,
-    avalanche_rounds: usize,
-    collision_log_population: usize,
+  -w, --width BITS            state width: 16, 32, or 64 bits (default 32)
+  -r, --rotations LIST        comma-separated rotation constants, two per
+                                round (default for 32 bits: 2,5,7,4,1,2,
+                                required for other widths)
+      --rounds N              number of rounds (default: one per pair of
+                                rotations), cycling through the rotations
+                                if there are more rounds than pairs
+  -p, --patterns LIST         comma-separated bit patterns to test
+                                (default: all of them, in this order):
+                                random, counting, counting-rev, combos,
+                                combos-rev, combos-inv, single-bit
+      --random-samples N      samples for the random pattern (default 16384)
+      --counting-samples N    samples for the counting patterns (default 4096)
+      --combo-samples N       samples for the bit combination patterns
+                                (default 4096)
+      --order N               highest order of avalanche to measure, i.e. the
+                                most input bits flipped at once (default 4,
+                                or 2 for 64 bits)
+      --collision-population LOG2
+                              log2 of the number of items and buckets in the
+                                collision tests, less than the width, or 0 to
+                                skip them (default 20, or 12 for 16 bits)
+      --json                  output the results as JSON
+
+      --help                  display this help and exit
+
+Progress is printed to stderr.
+";
+
+/// How many samples a bit pattern's avalanche tests use.
+#[derive(Debug, Copy, Clone)]
+enum Samples {
+    Random,
+    Counting,
+    Combo,
+
+    /// One sample per bit of the state.
+    Width,
 }
 
-// Note that by changing the counting and bit-combo rounds, you're also changing
-// what is being tested to some extent.  With the random rounds, on the other
-// hand, cranking it up mainly just reduces variance.
-const RANDOM_ROUNDS: usize = 1 << 14;
-const COUNTING_ROUNDS: usize = 1 << 12;
-const BIT_COMBO_ROUNDS: usize = 1 << 12;
+struct BitPattern {
+    id: &'static str,
+    name: &'static str,
+
+    /// Generates the input for sample `i` of a state of the given width.
+    /// Bits above the width are masked off afterwards.
+    gen_function: fn(i: usize, width: usize) -> Bits,
 
+    samples: Samples,
+    collision_test: bool,
+}
+
+// Note that by changing the counting and bit-combo sample counts, you're also
+// changing what is being tested to some extent.  With the random samples, on
+// the other hand, cranking it up mainly just reduces variance.
 const PATTERNS: &[BitPattern] = &[
     BitPattern {
+        id: "random",
         name: "Random",
-        gen_function: &generate_random,
-        avalanche_rounds: RANDOM_ROUNDS,
-        collision_log_population: 20,
+        gen_function: |i, _| generate_random(i),
+        samples: Samples::Random,
+        collision_test: true,
     },
     BitPattern {
+        id: "counting",
         name: "Counting",
-        gen_function: &|i| i as State,
-        avalanche_rounds: COUNTING_ROUNDS,
-        collision_log_population: 20,
+        gen_function: |i, _| i as Bits,
+        samples: Samples::Counting,
+        collision_test: true,
     },
     BitPattern {
+        id: "counting-rev",
         name: "Counting bit-reversed",
-        gen_function: &|i| (i as State).reverse_bits(),
-        avalanche_rounds: COUNTING_ROUNDS,
-        collision_log_population: 20,
+        gen_function: |i, width| reverse_bits(i as Bits, width),
+        samples: Samples::Counting,
+        collision_test: true,
     },
     BitPattern {
+        id: "combos",
         name: "Bit combinations",
-        gen_function: &bit_combinations,
-        avalanche_rounds: BIT_COMBO_ROUNDS,
-        collision_log_population: 20,
+        gen_function: bit_combinations,
+        samples: Samples::Combo,
+        collision_test: true,
     },
     BitPattern {
+        id: "combos-rev",
         name: "Bit combinations bit-reversed",
-        gen_function: &|i| bit_combinations(i).reverse_bits(),
-        avalanche_rounds: BIT_COMBO_ROUNDS,
-        collision_log_population: 20,
+        gen_function: |i, width| reverse_bits(bit_combinations(i, width), width),
+        samples: Samples::Combo,
+        collision_test: true,
     },
     BitPattern {
+        id: "combos-inv",
         name: "Bit combinations inverted",
-        gen_function: &|i| !bit_combinations(i),
-        avalanche_rounds: BIT_COMBO_ROUNDS,
-        collision_log_population: 20,
+        gen_function: |i, width| !bit_combinations(i, width),
+        samples: Samples::Combo,
+        collision_test: true,
     },
     BitPattern {
+        id: "single-bit",
         name: "single-bit",
-        gen_function: &|i| 1 << i,
+        gen_function: |i, _| 1 << i,
 
-        // NOTE: because this test has a small, fixed number of rounds by its
+        // NOTE: because this test has a small, fixed number of samples by its
         // nature, the generated statistics should be interpreted a little
         // differently. In particular, even a very good mixing function is
         // unlikely to achieve "perfect" avalanche or BIC by this measure,
         // purely because it's impossible to collect enough samples to reduce
         // variance enough.
-        avalanche_rounds: 32,
+        samples: Samples::Width,
 
-        collision_log_population: 0,
+        collision_test: false,
     },
 ];
 
-fn collision_test<F>(log_buckets: usize, item_count: usize, gen: F)
+#[derive(Debug)]
+struct Options {
+    mixer: Mixer,
+    patterns: Vec<&'static BitPattern>,
+    random_samples: usize,
+    counting_samples: usize,
+    combo_samples: usize,
+    order: usize,
+    collision_log_population: usize,
+    json: bool,
+}
+
+impl std::fmt::Debug for BitPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id)
+    }
+}
+
+/// The results for a single bit pattern, for JSON output.
+#[derive(Serialize)]
+struct PatternResults {
+    pattern: &'static str,
+    name: &'static str,
+    collisions: Option<CollisionResults>,
+    stats: Report,
+}
+
+/// The results for all bit patterns, for JSON output.
+#[derive(Serialize)]
+struct Results<'a> {
+    width: usize,
+    rotations: &'a [[u32; 2]],
+    rounds: usize,
+    order: usize,
+    patterns: Vec<PatternResults>,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => return,
+        Err(message) => {
+            eprintln!("tiny_mixer: {}", message);
+            eprintln!("Try 'tiny_mixer --help' for more information.");
+            std::process::exit(2);
+        }
+    };
+    let mixer = &options.mixer;
+    let width = mixer.width;
+
+    if !options.json {
+        println!(
+            "{}-bit state, {} rounds, rotations {:?}",
+            width, mixer.rounds, mixer.rotations
+        );
+    }
+
+    let mut results = Vec::new();
+    for pattern in &options.patterns {
+        if !options.json {
+            println!("\n{}:", pattern.name);
+        }
+
+        let gen = |i| (pattern.gen_function)(i, width) & width_mask(width);
+        let collisions = if pattern.collision_test && options.collision_log_population > 0 {
+            let collisions = collision_test(
+                mixer,
+                options.collision_log_population,
+                1 << options.collision_log_population,
+                gen,
+            );
+            if !options.json {
+                collisions.print();
+            }
+            Some(collisions)
+        } else {
+            None
+        };
+
+        let samples = match pattern.samples {
+            Samples::Random => options.random_samples,
+            Samples::Counting => options.counting_samples,
+            Samples::Combo => options.combo_samples,
+            Samples::Width => width,
+        };
+        let chart = compute_stats(
+            width,
+            options.order,
+            gen,
+            |a, b| {
+                *b = mixer.mix(*a);
+            },
+            samples,
+        );
+        let report = chart.report();
+        if !options.json {
+            report.print();
+        }
+
+        results.push(PatternResults {
+            pattern: pattern.id,
+            name: pattern.name,
+            collisions,
+            stats: report,
+        });
+    }
+
+    if options.json {
+        let results = Results {
+            width,
+            rotations: &mixer.rotations,
+            rounds: mixer.rounds,
+            order: options.order,
+            patterns: results,
+        };
+        println!("{}", serde_json::to_string_pretty(&results).unwrap());
+    }
+}
+
+/// Parses the command line arguments.
+///
+/// Returns `Ok(None)` if the program should exit successfully without doing
+/// anything else (e.g. after printing `--help`).
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut width = 32;
+    let mut rotations = None;
+    let mut rounds = None;
+    let mut patterns = None;
+    let mut random_samples = 1 << 14;
+    let mut counting_samples = 1 << 12;
+    let mut combo_samples = 1 << 12;
+    let mut order = None;
+    let mut collision_log_population = None;
+    let mut json = false;
+
+    fn number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+        value
+            .parse()
+            .map_err(|_| format!("invalid value for '{}': '{}'", name, value))
+    }
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("option '{}' requires an argument", arg))
+        };
+
+        match arg.as_str() {
+            "-w" | "--width" => width = number(&arg, &value()?)?,
+            "-r" | "--rotations" => {
+                let list = value()?;
+                let numbers = list
+                    .split(',')
+                    .map(|n| number::<u32>(&arg, n))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if numbers.is_empty() || numbers.len() % 2 != 0 {
+                    return Err(format!(
+                        "'{}' needs an even number of rotation constants: '{}'",
+                        arg, list
+                    ));
+                }
+                rotations = Some(numbers.chunks(2).map(|pair| [pair[0], pair[1]]).collect());
+            }
+            "--rounds" => rounds = Some(number(&arg, &value()?)?),
+            "-p" | "--patterns" => {
+                let list = value()?;
+                let mut selected = Vec::new();
+                for id in list.split(',') {
+                    let pattern = PATTERNS
+                        .iter()
+                        .find(|pattern| pattern.id == id)
+                        .ok_or_else(|| format!("unknown bit pattern '{}'", id))?;
+                    selected.push(pattern);
+                }
+                patterns = Some(selected);
+            }
+            "--random-samples" => random_samples = number(&arg, &value()?)?,
+            "--counting-samples" => counting_samples = number(&arg, &value()?)?,
+            "--combo-samples" => combo_samples = number(&arg, &value()?)?,
+            "--order" => order = Some(number(&arg, &value()?)?),
+            "--collision-population" => collision_log_population = Some(number(&arg, &value()?)?),
+            "--json" => json = true,
+            "--help" => {
+                print!("{}", USAGE);
+                return Ok(None);
+            }
+            _ => return Err(format!("unrecognized option '{}'", arg)),
+        }
+    }
+
+    if ![16, 32, 64].contains(&width) {
+        return Err(format!("unsupported width: {} bits", width));
+    }
+    let rotations: Vec<[u32; 2]> = match rotations {
+        Some(rotations) => rotations,
+        None if width == 32 => DEFAULT_ROTATIONS_32.to_vec(),
+        None => return Err(format!("--rotations is required for {}-bit states", width)),
+    };
+    let lane_bits = width / 4;
+    if let Some(&rot) = rotations
+        .iter()
+        .flatten()
+        .find(|&&rot| rot as usize >= lane_bits)
+    {
+        return Err(format!(
+            "rotation {} is too large for {}-bit lanes",
+            rot, lane_bits
+        ));
+    }
+
+    let order = order.unwrap_or(if width == 64 { 2 } else { 4 });
+    if order == 0 || order > width {
+        return Err(format!("invalid avalanche order: {}", order));
+    }
+    // The xored bucket test needs the high and low bits to be different bits,
+    // so the population has to be smaller than the state.
+    let collision_log_population = collision_log_population.unwrap_or((width - 4).min(20));
+    if collision_log_population >= width {
+        return Err(format!(
+            "collision population of 2^{} is too large for {}-bit states",
+            collision_log_population, width
+        ));
+    }
+
+    Ok(Some(Options {
+        mixer: Mixer {
+            width,
+            rounds: rounds.unwrap_or(rotations.len()),
+            rotations,
+        },
+        patterns: patterns.unwrap_or_else(|| PATTERNS.iter().collect()),
+        random_samples,
+        counting_samples,
+        combo_samples,
+        order,
+        collision_log_population,
+        json,
+    }))
+}
+
+/// Reverses the order of the low `width` bits of `n`.
+fn reverse_bits(n: Bits, width: usize) -> Bits {
+    n.reverse_bits() >> (Bits::BITS as usize - width)
+}
+
+/// The results of a collision test using one way of picking buckets.
+#[derive(Debug, Copy, Clone, Serialize)]
+struct BucketStats {
+    collisions: usize,
+    smallest_bucket: usize,
+    largest_bucket: usize,
+}
+
+/// The results of a collision test.
+#[derive(Debug, Copy, Clone, Serialize)]
+struct CollisionResults {
+    buckets: usize,
+    items: usize,
+    high_bits: BucketStats,
+    low_bits: BucketStats,
+    xored_bits: BucketStats,
+}
+
+impl CollisionResults {
+    fn print(&self) {
+        println!(
+            "    Collision tests: {} buckets and {} items:",
+            self.buckets, self.items
+        );
+        for (name, stats) in [
+            ("high bits", &self.high_bits),
+            ("low bits", &self.low_bits),
+            ("xored high and low bits", &self.xored_bits),
+        ] {
+            println!("        Using {}:", name);
+            println!(
+                "            Collisions: {}
+            Smallest bucket: {}
+            Largest bucket:  {}",
+                stats.collisions, stats.smallest_bucket, stats.largest_bucket,
+            );
+        }
+    }
+}
+
+fn collision_test<F>(
+    mixer: &Mixer,
+    log_buckets: usize,
+    item_count: usize,
+    gen: F,
+) -> CollisionResults
 where
-    F: Fn(usize) -> State,
+    F: Fn(usize) -> Bits,
 {
     let bucket_count = 1 << log_buckets;
+    let high_shift = mixer.width - log_buckets;
     let mut buckets_low_bits = vec![0u32; bucket_count];
     let mut buckets_high_bits = vec![0u32; bucket_count];
     let mut buckets_combined_bits = vec![0u32; bucket_count];
 
     for i in 0..item_count {
-        let mut state = gen(i);
+        let state = mixer.mix(gen(i));
 
-        mix(&mut state);
         let n = state as usize;
         buckets_low_bits[n % bucket_count] += 1;
-        buckets_high_bits[(n >> (32 - log_buckets)) % bucket_count] += 1;
-        let n = n ^ (n >> (32 - log_buckets));
+        buckets_high_bits[(n >> high_shift) % bucket_count] += 1;
+        let n = n ^ (n >> high_shift);
         buckets_combined_bits[n % bucket_count] += 1;
     }
 
@@ -159,22 +456,18 @@ where
             max_count = b.max(max_count);
         }
 
-        println!(
-            "            Collisions: {}
-            Smallest bucket: {}
-            Largest bucket:  {}",
-            collisions, min_count, max_count,
-        );
+        BucketStats {
+            collisions,
+            smallest_bucket: min_count,
+            largest_bucket: max_count,
+        }
     };
 
-    println!(
-        "    Collision tests: {} buckets and {} items:",
-        bucket_count, item_count
-    );
-    println!("        Using high bits:");
-    do_stats(&buckets_low_bits);
-    println!("        Using low bits:");
-    do_stats(&buckets_high_bits);
-    println!("        Using xored high and low bits:");
-    do_stats(&buckets_combined_bits);
+    CollisionResults {
+        buckets: bucket_count,
+        items: item_count,
+        high_bits: do_stats(&buckets_high_bits),
+        low_bits: do_stats(&buckets_low_bits),
+        xored_bits: do_stats(&buckets_combined_bits),
+    }
 }
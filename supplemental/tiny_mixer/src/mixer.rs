@@ -0,0 +1,48 @@
+use crate::stats::{rotate_left, width_mask, Bits};
+
+/// The rotation constants for the 32-bit mixer.
+///
+/// These have been tuned to give similar diffusion (relative to total bits)
+/// as TentHash's full-size mixing function.  Running nine rounds instead of
+/// six (i.e. repeating the first three) gives full diffusion.
+pub const DEFAULT_ROTATIONS_32: &[[u32; 2]] = &[[2, 5], [7, 4], [1, 2], [2, 5], [7, 4], [1, 2]];
+
+/// Same construction as TentHash's mixing function, just with a smaller
+/// state: four lanes of `width / 4` bits each, rather than of 64 bits.
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    /// The width of the whole state, in bits.
+    pub width: usize,
+
+    /// The rotation constants for each round.
+    pub rotations: Vec<[u32; 2]>,
+
+    /// The number of rounds.  If this is more than the number of rotation
+    /// pairs, the rotations are cycled through again from the start.
+    pub rounds: usize,
+}
+
+impl Mixer {
+    pub fn mix(&self, state: Bits) -> Bits {
+        let lane_bits = self.width / 4;
+        let lane_mask = width_mask(lane_bits);
+        let mut lanes = [0; 4];
+        for (i, lane) in lanes.iter_mut().enumerate() {
+            *lane = (state >> (i * lane_bits)) & lane_mask;
+        }
+
+        for rot in self.rotations.iter().cycle().take(self.rounds) {
+            lanes[0] = lanes[0].wrapping_add(lanes[2]) & lane_mask;
+            lanes[1] = lanes[1].wrapping_add(lanes[3]) & lane_mask;
+            lanes[2] = rotate_left(lanes[2], rot[0], lane_bits) ^ lanes[0];
+            lanes[3] = rotate_left(lanes[3], rot[1], lane_bits) ^ lanes[1];
+            lanes.swap(0, 1);
+        }
+
+        let mut state = 0;
+        for (i, lane) in lanes.iter().enumerate() {
+            state |= lane << (i * lane_bits);
+        }
+        state
+    }
+}
@@ -1,56 +1,82 @@
 use nanorand::{Rng, WyRand};
+use serde::Serialize;
 
-type Bits = u32;
-const SIZE: usize = std::mem::size_of::<Bits>() * 8;
-
-// Size needed for the higher-order avalanche test.
-//
-// This is set to measure avalanche up to order 4 (flipping all combinations of
-// up to 4 bits).  You can set `ORDER` higher to test even higher orders, but be
-// forewarned that the time and memory requirements rapidly increase.  `ORDER =
-// 1` is a standerd avalanche test.
-const HIGHER_ORDER_SIZE: usize = {
-    const ORDER: usize = 4;
-
-    // The below amounts to just `(1..=ORDER).map(|i| binomial(32, i)).sum()`,
-    // but all the niceties that allow this to be written better (even for
-    // loops!) aren't allowed in a const context yet.
-    let mut combo_count = 0;
-    let mut i = 1;
-    while i <= ORDER {
-        combo_count += binomial(32, i);
-        i += 1;
+/// The type used to hold states of any supported width.  Bits above the
+/// width are always zero.
+pub type Bits = u64;
+
+/// Returns a mask of the low `width` bits.
+pub fn width_mask(width: usize) -> Bits {
+    if width >= Bits::BITS as usize {
+        Bits::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Rotates the low `width` bits of `n` left by `amount`.
+pub fn rotate_left(n: Bits, amount: u32, width: usize) -> Bits {
+    let amount = amount as usize % width;
+    if amount == 0 {
+        return n;
     }
-    combo_count
-};
+    ((n << amount) | (n >> (width - amount))) & width_mask(width)
+}
 
 pub struct Stats {
+    // The width of the mixed state, in bits.
+    pub width: usize,
+
     // The number of samples accumulated.  Or put another way, the number of
     // rounds used to generate the chart.
     pub sample_count: usize,
 
-    // For every input bit, the BIC quadrants for each pair of output bits.
+    // For every input bit, the BIC quadrants for each pair of output bits,
+    // indexed by `in_bit * width * (width - 1) + pair`.
     // Note: this table is actually twice as large as it needs to be, because it
     // stores each output pair twice.  We just live with that because the code
     // is easier to write this way, and it doesn't alter the results.
-    pub bic_chart: [[[u32; 4]; SIZE * (SIZE - 1)]; SIZE],
+    pub bic_chart: Vec<[u32; 4]>,
 
     // Each element is a count of the number of bit flips for a given in/out
-    // bit set, where "in" can be up to two bits rather than just one. The first
-    // 32 rows are single bit flips, and the remainder are all combinations of
-    // two-bit flips.
+    // bit set, where "in" can be up to `order` bits rather than just one. The
+    // first `width` rows are single bit flips, and the remainder are all
+    // combinations of two-bit flips, then three-bit flips, and so on.
     //
     // This is basically just a more advanced avalanche chart, that accounts for
-    // one additional higher-order avalanche check.
-    pub avalanche_chart: Vec<[u32; SIZE]>,
+    // higher-order avalanche as well.
+    pub avalanche_chart: Vec<Vec<u32>>,
+}
+
+/// The minimum, average, and maximum of a statistic.
+#[derive(Debug, Copy, Clone, Serialize)]
+pub struct MinAvgMax {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+/// A summary of the chart statistics, from `Stats::report()`.
+#[derive(Debug, Copy, Clone, Serialize)]
+pub struct Report {
+    pub sample_count: usize,
+    pub bias: MinAvgMax,
+    pub diffusion: MinAvgMax,
+    pub diffusion_entropy: MinAvgMax,
+    pub bic_deviation: MinAvgMax,
 }
 
 impl Stats {
-    pub fn new() -> Self {
+    /// Creates an empty chart for a `width`-bit state, measuring avalanche
+    /// up to the given order (flipping all combinations of up to `order`
+    /// bits).  `order = 1` is a standard avalanche test.
+    pub fn new(width: usize, order: usize) -> Self {
+        let higher_order_size = (1..=order).map(|i| binomial(width, i)).sum();
         Self {
+            width,
             sample_count: 0,
-            bic_chart: [[[0; 4]; SIZE * (SIZE - 1)]; SIZE],
-            avalanche_chart: vec![[0; SIZE]; HIGHER_ORDER_SIZE],
+            bic_chart: vec![[0; 4]; width * width * (width - 1)],
+            avalanche_chart: vec![vec![0; width]; higher_order_size],
         }
     }
 
@@ -188,7 +214,8 @@ impl Stats {
     /// This gives a good measure of how statistically independent the effects
     /// of flipping a given input are, with 0.0 being best and 1.0 being worst.
     pub fn row_bic_avg_deviation(&self, in_bit_idx: usize) -> f64 {
-        let bic = self.bic_chart[in_bit_idx];
+        let pair_count = self.width * (self.width - 1);
+        let bic = &self.bic_chart[(in_bit_idx * pair_count)..((in_bit_idx + 1) * pair_count)];
 
         let mut sum = 0.0;
         for [a, b, c, d] in bic.iter() {
@@ -197,13 +224,13 @@ impl Stats {
 
             sum += (max - min) as f64 / max as f64;
         }
-        sum / (SIZE * (SIZE - 1)) as f64
+        sum / pair_count as f64
     }
 
     /// The minimum of `row_bic_avg_deviation()` across all input bits.
     pub fn min_bic_deviation(&self) -> f64 {
         let mut n = 999.0_f64;
-        for i in 0..SIZE {
+        for i in 0..self.width {
             n = n.min(self.row_bic_avg_deviation(i));
         }
         n
@@ -212,24 +239,52 @@ impl Stats {
     /// The average of `row_bic_avg_deviation()` across all input bits.
     pub fn avg_bic_deviation(&self) -> f64 {
         let mut n = 0.0;
-        for i in 0..SIZE {
+        for i in 0..self.width {
             n += self.row_bic_avg_deviation(i);
         }
-        n / SIZE as f64
+        n / self.width as f64
     }
 
     /// The maximum of `row_bic_avg_deviation()` across all input bits.
     pub fn max_bic_deviation(&self) -> f64 {
         let mut n = 0.0_f64;
-        for i in 0..SIZE {
+        for i in 0..self.width {
             n = n.max(self.row_bic_avg_deviation(i));
         }
         n
     }
 
-    /// Prints a summary report of the chart statistics.
-    #[allow(dead_code)]
-    pub fn print_report(&self) {
+    /// Computes a summary of the chart statistics.
+    pub fn report(&self) -> Report {
+        Report {
+            sample_count: self.sample_count,
+            bias: MinAvgMax {
+                min: self.min_bias(),
+                avg: self.average_bias(),
+                max: self.max_bias(),
+            },
+            diffusion: MinAvgMax {
+                min: self.min_input_bit_combo_diffusion(),
+                avg: self.avg_input_bit_combo_diffusion(),
+                max: self.max_input_bit_combo_diffusion(),
+            },
+            diffusion_entropy: MinAvgMax {
+                min: self.min_input_bit_combo_entropy(),
+                avg: self.avg_input_bit_combo_entropy(),
+                max: self.max_input_bit_combo_entropy(),
+            },
+            bic_deviation: MinAvgMax {
+                min: self.min_bic_deviation(),
+                avg: self.avg_bic_deviation(),
+                max: self.max_bic_deviation(),
+            },
+        }
+    }
+}
+
+impl Report {
+    /// Prints the report in human-readable form.
+    pub fn print(&self) {
         println!(
             "    Bias (lower is better):
         Min: {:0.2}
@@ -237,7 +292,7 @@ impl Stats {
         Max: {:0.2}
     Diffusion (higher is better):
         Min: {:0.1} bits
-        Avg: {:0.1} bits 
+        Avg: {:0.1} bits
         Max: {:0.1} bits
     Diffusion Entropy (higher is better):
         Min: {:0.1} bits
@@ -247,18 +302,18 @@ impl Stats {
         Min: {:0.4}
         Avg: {:0.4}
         Max: {:0.4}",
-            self.min_bias(),
-            self.average_bias(),
-            self.max_bias(),
-            self.min_input_bit_combo_diffusion(),
-            self.avg_input_bit_combo_diffusion(),
-            self.max_input_bit_combo_diffusion(),
-            self.min_input_bit_combo_entropy(),
-            self.avg_input_bit_combo_entropy(),
-            self.max_input_bit_combo_entropy(),
-            self.min_bic_deviation(),
-            self.avg_bic_deviation(),
-            self.max_bic_deviation(),
+            self.bias.min,
+            self.bias.avg,
+            self.bias.max,
+            self.diffusion.min,
+            self.diffusion.avg,
+            self.diffusion.max,
+            self.diffusion_entropy.min,
+            self.diffusion_entropy.avg,
+            self.diffusion_entropy.max,
+            self.bic_deviation.min,
+            self.bic_deviation.avg,
+            self.bic_deviation.max,
         );
     }
 }
@@ -266,6 +321,8 @@ impl Stats {
 /// Computes mixing statiastics for a given mix/absorb function, using a
 /// provided input generator.
 ///
+/// - `width`: the width of the state being mixed, in bits.
+/// - `order`: the highest order of avalanche to measure.  See `Stats::new()`.
 /// - `generate_input`: function that takes a seed and generates an input block.
 ///   The result should be deterministic based on the seed.  Note that the seed
 ///   starts from zero, and simply increments each round.
@@ -274,45 +331,55 @@ impl Stats {
 ///   function, and should instead be ignored and overwritten.  In other words,
 ///   it is purely an out paramater, not an in-out parameter.
 /// - `rounds`: how many test rounds to perform to produce the estimated chart.
-pub fn compute_stats<F1, F2>(generate_input: F1, mix: F2, rounds: usize) -> Stats
+///
+/// Progress is printed to stderr.
+pub fn compute_stats<F1, F2>(
+    width: usize,
+    order: usize,
+    generate_input: F1,
+    mix: F2,
+    rounds: usize,
+) -> Stats
 where
     F1: Fn(usize) -> Bits,
     F2: Fn(&Bits, &mut Bits),
 {
-    let mut chart = Stats::new();
+    let mut chart = Stats::new(width, order);
+    let higher_order_size = chart.avalanche_chart.len();
+    let mask = width_mask(width);
 
     for round in 0..rounds {
-        if (round % (10000 / HIGHER_ORDER_SIZE).max(1)) == 0 {
+        if (round % (10000 / higher_order_size).max(1)) == 0 {
             use std::io::Write;
-            print!(
+            eprint!(
                 "\r                                \rRound {} / {}",
                 round, rounds
             );
-            let _ = std::io::stdout().flush();
+            let _ = std::io::stderr().flush();
         }
 
-        let input = generate_input(round);
+        let input = generate_input(round) & mask;
         let mut output = 0usize as Bits;
         mix(&input, &mut output);
 
         // Avalanche.
-        for flip_idx in 0..HIGHER_ORDER_SIZE {
+        for flip_idx in 0..higher_order_size {
             let mut input_tweaked = input;
-            input_tweaked ^= bit_combinations(flip_idx + 1);
+            input_tweaked ^= bit_combinations(flip_idx + 1, width);
             let mut output_tweaked = 0usize as Bits;
             mix(&input_tweaked, &mut output_tweaked);
 
             let flips = output ^ output_tweaked;
 
             // Avalanche.
-            for out_bit_idx in 0..SIZE {
+            for out_bit_idx in 0..width {
                 let flipped = (flips & (1 << out_bit_idx)) != 0;
                 chart.avalanche_chart[flip_idx][out_bit_idx] += flipped as u32;
             }
         }
 
         // Bit independence criterion.
-        for in_bit_idx in 0..SIZE {
+        for in_bit_idx in 0..width {
             let mut input_tweaked = input;
             input_tweaked ^= 1 << in_bit_idx;
             let mut output_tweaked = 0usize as Bits;
@@ -320,21 +387,21 @@ where
 
             let flips = output ^ output_tweaked;
 
-            for i in 0..(SIZE - 1) {
-                let flips_s = flips.rotate_left(i as u32 + 1);
+            for i in 0..(width - 1) {
+                let flips_s = rotate_left(flips, i as u32 + 1, width);
 
                 let both = flips & flips_s;
                 let neither = !flips & !flips_s;
                 let only_left = flips & !flips_s;
                 let only_right = !flips & flips_s;
 
-                for j in 0..SIZE {
+                for j in 0..width {
                     let mask = 1 << j;
-                    let k = j * (SIZE - 1) + i;
-                    chart.bic_chart[in_bit_idx][k][0] += ((both & mask) != 0) as u32;
-                    chart.bic_chart[in_bit_idx][k][1] += ((neither & mask) != 0) as u32;
-                    chart.bic_chart[in_bit_idx][k][2] += ((only_left & mask) != 0) as u32;
-                    chart.bic_chart[in_bit_idx][k][3] += ((only_right & mask) != 0) as u32;
+                    let k = (in_bit_idx * width + j) * (width - 1) + i;
+                    chart.bic_chart[k][0] += ((both & mask) != 0) as u32;
+                    chart.bic_chart[k][1] += ((neither & mask) != 0) as u32;
+                    chart.bic_chart[k][2] += ((only_left & mask) != 0) as u32;
+                    chart.bic_chart[k][3] += ((only_right & mask) != 0) as u32;
                 }
             }
         }
@@ -342,7 +409,7 @@ where
         chart.sample_count += 1;
     }
 
-    print!("\r                                \r");
+    eprint!("\r                                \r");
 
     chart
 }
@@ -362,8 +429,8 @@ pub fn p_to_entropy(p: f64) -> f64 {
 
 //-------------------------------------------------------------
 
-/// Generates a random data block.
-#[allow(dead_code)]
+/// Generates a random data block.  Callers should mask off any bits beyond
+/// the state width.
 pub fn generate_random(seed: usize) -> Bits {
     fn mix64(mut n: u64) -> u64 {
         // Break zero sensitivity.
@@ -383,21 +450,21 @@ pub fn generate_random(seed: usize) -> Bits {
     WyRand::new_seed(mix64(seed as u64)).generate::<Bits>()
 }
 
-/// Computes the nth bit combination, ordered by first no set bits, then all
-/// combinations of one set bit, then two set bits, and so on.
-#[allow(dead_code)]
-pub fn bit_combinations(index: usize) -> u32 {
-    let mut n = index & 0xffffffff;
+/// Computes the nth bit combination of a `width`-bit state, ordered by first
+/// no set bits, then all combinations of one set bit, then two set bits, and
+/// so on.
+pub fn bit_combinations(index: usize, width: usize) -> Bits {
+    let mut n = index & width_mask(width) as usize;
     let mut bits = 0;
-    let mut combos = binomial(32, bits);
+    let mut combos = binomial(width, bits);
     while n >= combos {
         n -= combos;
         bits += 1;
-        combos = binomial(32, bits);
+        combos = binomial(width, bits);
     }
 
     let mut result = 0;
-    let mut t = 32;
+    let mut t = width;
     while t > 0 && bits > 0 {
         let y = if t > bits { binomial(t - 1, bits) } else { 0 };
 
@@ -413,16 +480,18 @@ pub fn bit_combinations(index: usize) -> u32 {
     result
 }
 
-const fn binomial(n: usize, k: usize) -> usize {
+/// Computes the binomial coefficient `n choose k`.
+pub fn binomial(n: usize, k: usize) -> usize {
     if k > n {
         return 0;
     }
+    let k = k.min(n - k);
 
-    if k == 0 {
-        1
-    } else if k > (n / 2) {
-        binomial(n, n - k)
-    } else {
-        n * binomial(n - 1, k - 1) / k
+    // Each step computes `(n - k + i + 1) choose (i + 1)`, so the division is
+    // always exact.
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * (n - k + i + 1) as u128 / (i + 1) as u128;
     }
+    result as usize
 }
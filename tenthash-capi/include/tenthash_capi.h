@@ -0,0 +1,122 @@
+/* TentHash C API.  Licensed under MIT or Apache 2.0, at your option. */
+
+#ifndef TENTHASH_CAPI_H
+#define TENTHASH_CAPI_H
+
+/* Generated with cbindgen from src/lib.rs.  Don't edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The size of a TentHash digest, in bytes.
+ */
+#define TENTHASH_DIGEST_SIZE 20
+
+/**
+ * The size of `tenthash_hasher`, in bytes.
+ *
+ * This is part of the ABI, and won't change without a major version bump.
+ */
+#define TENTHASH_HASHER_SIZE 128
+
+/**
+ * An incremental hasher, allocated on the heap.
+ *
+ * Create with `tenthash_new()`, and destroy with `tenthash_free()`.
+ */
+typedef struct tenthash_state tenthash_state;
+
+/**
+ * An incremental hasher with a fixed size, for stack allocation or
+ * embedding in other structs.
+ *
+ * Initialize with `tenthash_hasher_init()` before use.  Needs no cleanup.
+ * The contents are private, and must not be accessed directly.
+ */
+typedef struct tenthash_hasher {
+  uint64_t opaque[16];
+} tenthash_hasher;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Computes the TentHash digest of `len` bytes at `data` in one go.
+ *
+ * `out` must be non-NULL and point to `TENTHASH_DIGEST_SIZE` writable bytes,
+ * which the digest is written to.  `data` may only be NULL if `len` is zero.
+ */
+void tenthash_hash(const uint8_t *data, size_t len, uint8_t *out);
+
+/**
+ * Creates a new incremental hasher.
+ *
+ * The returned hasher must be destroyed with `tenthash_free()`.  Aborts if
+ * allocation fails, so the result is never NULL.
+ */
+struct tenthash_state *tenthash_new(void);
+
+/**
+ * Appends `len` bytes at `data` to the data being hashed.
+ *
+ * `state` must be a hasher returned by `tenthash_new()` that hasn't been
+ * freed yet, and must not be NULL.  `data` may only be NULL if `len` is
+ * zero.
+ */
+void tenthash_update(struct tenthash_state *state, const uint8_t *data, size_t len);
+
+/**
+ * Writes the digest of the data hashed so far to `out`.
+ *
+ * `state` must be a hasher returned by `tenthash_new()` that hasn't been
+ * freed yet, and must not be NULL.  `out` must be non-NULL and point to
+ * `TENTHASH_DIGEST_SIZE` writable bytes.  The hasher is left unchanged, so
+ * more data can still be appended afterwards.
+ */
+void tenthash_finalize(const struct tenthash_state *state, uint8_t *out);
+
+/**
+ * Destroys a hasher created with `tenthash_new()`.
+ *
+ * `state` must be either NULL, in which case this does nothing, or a hasher
+ * returned by `tenthash_new()` that hasn't been freed yet.  It must not be
+ * used again afterwards.
+ */
+void tenthash_free(struct tenthash_state *state);
+
+/**
+ * Initializes (or resets) a fixed-size hasher.
+ *
+ * `hasher` must be non-NULL, and point to a writable `tenthash_hasher` that's
+ * suitably aligned for its type (as any `tenthash_hasher` variable or struct
+ * field is).  It doesn't need to have been initialized before.
+ */
+void tenthash_hasher_init(struct tenthash_hasher *hasher);
+
+/**
+ * Appends `len` bytes at `data` to the data being hashed.
+ *
+ * `hasher` must be non-NULL, suitably aligned, and point to a
+ * `tenthash_hasher` that has been initialized with `tenthash_hasher_init()`.
+ * `data` may only be NULL if `len` is zero.
+ */
+void tenthash_hasher_update(struct tenthash_hasher *hasher, const uint8_t *data, size_t len);
+
+/**
+ * Writes the digest of the data hashed so far to `out`.
+ *
+ * `hasher` must be non-NULL, suitably aligned, and point to a
+ * `tenthash_hasher` that has been initialized with `tenthash_hasher_init()`.
+ * `out` must be non-NULL and point to `TENTHASH_DIGEST_SIZE` writable bytes.
+ * The hasher is left unchanged, so more data can still be appended
+ * afterwards.
+ */
+void tenthash_hasher_finalize(const struct tenthash_hasher *hasher, uint8_t *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TENTHASH_CAPI_H */
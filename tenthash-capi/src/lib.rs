@@ -0,0 +1,152 @@
+//! C API for the Rust implementation of TentHash.
+//!
+//! This builds a shared and a static library exposing TentHash to C and
+//! other languages with a C FFI.  The C declarations are in
+//! `include/tenthash_capi.h`, which is generated from this file with
+//! [cbindgen](https://github.com/mozilla/cbindgen).  See `README.md` for
+//! details.
+//!
+//! There are three ways to compute a hash:
+//!
+//! - `tenthash_hash()`, for hashing a contiguous buffer in one go.
+//! - `tenthash_state`, a heap-allocated incremental hasher with
+//!   `tenthash_new()`, `tenthash_update()`, `tenthash_finalize()`, and
+//!   `tenthash_free()`.
+//! - `tenthash_hasher`, a fixed-size incremental hasher that can live on the
+//!   stack or be embedded in other structs, with `tenthash_hasher_init()`,
+//!   `tenthash_hasher_update()`, and `tenthash_hasher_finalize()`.
+
+// These are all thin wrappers, and the safety requirements are documented in
+// the header.
+#![allow(clippy::missing_safety_doc)]
+
+use core::mem::{align_of, size_of};
+
+use tenthash::TentHash;
+
+/// The size of a TentHash digest, in bytes.
+pub const TENTHASH_DIGEST_SIZE: usize = 20;
+
+/// The size of `tenthash_hasher`, in bytes.
+///
+/// This is part of the ABI, and won't change without a major version bump.
+pub const TENTHASH_HASHER_SIZE: usize = 128;
+
+/// Computes the TentHash digest of `len` bytes at `data` in one go.
+///
+/// `out` must be non-NULL and point to `TENTHASH_DIGEST_SIZE` writable bytes,
+/// which the digest is written to.  `data` may only be NULL if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_hash(data: *const u8, len: usize, out: *mut u8) {
+    write_digest(tenthash::hash(bytes(data, len)), out);
+}
+
+/// An incremental hasher, allocated on the heap.
+///
+/// Create with `tenthash_new()`, and destroy with `tenthash_free()`.
+pub struct TentHashState(TentHash);
+
+/// Creates a new incremental hasher.
+///
+/// The returned hasher must be destroyed with `tenthash_free()`.  Aborts if
+/// allocation fails, so the result is never NULL.
+#[no_mangle]
+pub extern "C" fn tenthash_new() -> *mut TentHashState {
+    Box::into_raw(Box::new(TentHashState(TentHash::new())))
+}
+
+/// Appends `len` bytes at `data` to the data being hashed.
+///
+/// `state` must be a hasher returned by `tenthash_new()` that hasn't been
+/// freed yet, and must not be NULL.  `data` may only be NULL if `len` is
+/// zero.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_update(state: *mut TentHashState, data: *const u8, len: usize) {
+    (*state).0.update(bytes(data, len));
+}
+
+/// Writes the digest of the data hashed so far to `out`.
+///
+/// `state` must be a hasher returned by `tenthash_new()` that hasn't been
+/// freed yet, and must not be NULL.  `out` must be non-NULL and point to
+/// `TENTHASH_DIGEST_SIZE` writable bytes.  The hasher is left unchanged, so
+/// more data can still be appended afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_finalize(state: *const TentHashState, out: *mut u8) {
+    write_digest((*state).0.finalize(), out);
+}
+
+/// Destroys a hasher created with `tenthash_new()`.
+///
+/// `state` must be either NULL, in which case this does nothing, or a hasher
+/// returned by `tenthash_new()` that hasn't been freed yet.  It must not be
+/// used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_free(state: *mut TentHashState) {
+    if !state.is_null() {
+        drop(Box::from_raw(state));
+    }
+}
+
+/// An incremental hasher with a fixed size, for stack allocation or
+/// embedding in other structs.
+///
+/// Initialize with `tenthash_hasher_init()` before use.  Needs no cleanup.
+/// The contents are private, and must not be accessed directly.
+#[repr(C)]
+pub struct TentHashHasher {
+    opaque: [u64; 16],
+}
+
+const _: () = assert!(size_of::<TentHashHasher>() == TENTHASH_HASHER_SIZE);
+const _: () = assert!(size_of::<TentHash>() <= size_of::<TentHashHasher>());
+const _: () = assert!(align_of::<TentHash>() <= align_of::<TentHashHasher>());
+
+/// Initializes (or resets) a fixed-size hasher.
+///
+/// `hasher` must be non-NULL, and point to a writable `tenthash_hasher` that's
+/// suitably aligned for its type (as any `tenthash_hasher` variable or struct
+/// field is).  It doesn't need to have been initialized before.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_hasher_init(hasher: *mut TentHashHasher) {
+    hasher.cast::<TentHash>().write(TentHash::new());
+}
+
+/// Appends `len` bytes at `data` to the data being hashed.
+///
+/// `hasher` must be non-NULL, suitably aligned, and point to a
+/// `tenthash_hasher` that has been initialized with `tenthash_hasher_init()`.
+/// `data` may only be NULL if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_hasher_update(
+    hasher: *mut TentHashHasher,
+    data: *const u8,
+    len: usize,
+) {
+    (*hasher.cast::<TentHash>()).update(bytes(data, len));
+}
+
+/// Writes the digest of the data hashed so far to `out`.
+///
+/// `hasher` must be non-NULL, suitably aligned, and point to a
+/// `tenthash_hasher` that has been initialized with `tenthash_hasher_init()`.
+/// `out` must be non-NULL and point to `TENTHASH_DIGEST_SIZE` writable bytes.
+/// The hasher is left unchanged, so more data can still be appended
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn tenthash_hasher_finalize(hasher: *const TentHashHasher, out: *mut u8) {
+    write_digest((*hasher.cast::<TentHash>()).finalize(), out);
+}
+
+/// Makes a slice from a C pointer and length, allowing NULL for empty data.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        core::slice::from_raw_parts(data, len)
+    }
+}
+
+unsafe fn write_digest(digest: [u8; TENTHASH_DIGEST_SIZE], out: *mut u8) {
+    core::ptr::copy_nonoverlapping(digest.as_ptr(), out, TENTHASH_DIGEST_SIZE);
+}
@@ -0,0 +1,68 @@
+#include <stdio.h>
+#include <string.h>
+#include "tenthash_capi.h"
+
+static const uint8_t inputs[][64] = {
+    {0},
+    {0},
+    "0123456789",
+    "abcdefghijklmnopqrstuvwxyz",
+    "This string is exactly 32 bytes.",
+    "The quick brown fox jumps over the lazy dog.",
+};
+static const size_t inputs_len[] = {0, 1, 10, 26, 32, 44};
+static const uint8_t digests[][TENTHASH_DIGEST_SIZE] = {
+    {0x68, 0xc8, 0x21, 0x3b, 0x7a, 0x76, 0xb8, 0xed, 0x26, 0x7d, 0xdd, 0xb3, 0xd8, 0x71, 0x7b, 0xb3, 0xb6, 0xe7, 0xcc, 0x0a},
+    {0x3c, 0xf6, 0x83, 0x3c, 0xca, 0x9c, 0x4d, 0x5e, 0x21, 0x13, 0x18, 0x57, 0x7b, 0xab, 0x74, 0xbf, 0x12, 0xa4, 0xf0, 0x90},
+    {0xa7, 0xd3, 0x24, 0xbd, 0xe0, 0xbf, 0x6c, 0xe3, 0x42, 0x77, 0x01, 0x62, 0x8f, 0x0f, 0x8f, 0xc3, 0x29, 0xc2, 0xa1, 0x16},
+    {0xf1, 0xbe, 0x4b, 0xe1, 0xa0, 0xf9, 0xea, 0xe6, 0x50, 0x0f, 0xb2, 0xf6, 0xb6, 0x4f, 0x3d, 0xaa, 0x39, 0x90, 0xac, 0x1a},
+    {0xf7, 0xc5, 0xe4, 0x76, 0x3d, 0x89, 0xbd, 0xdc, 0xe3, 0x3e, 0x97, 0x71, 0x2b, 0x71, 0x2d, 0x86, 0x9a, 0xab, 0xcf, 0xe9},
+    {0xde, 0x77, 0xf1, 0xc1, 0x34, 0x22, 0x8b, 0xe1, 0xb5, 0xb2, 0x5c, 0x94, 0x1d, 0x51, 0x02, 0xf8, 0x7f, 0x3e, 0x6d, 0x39},
+};
+#define NUM_VECTORS (sizeof(inputs_len) / sizeof(inputs_len[0]))
+
+static int check(const char *what, size_t i, const uint8_t *output) {
+    if (memcmp(output, digests[i], TENTHASH_DIGEST_SIZE) != 0) {
+        printf("Failed test vector %zu (%s).\n", i + 1, what);
+        return 1;
+    }
+    return 0;
+}
+
+int main(void) {
+    int failures = 0;
+    uint8_t output[TENTHASH_DIGEST_SIZE];
+
+    for (size_t i = 0; i < NUM_VECTORS; i++) {
+        // One-shot.
+        tenthash_hash(inputs[i], inputs_len[i], output);
+        failures += check("tenthash_hash", i, output);
+
+        // Heap-allocated state, fed one byte at a time.
+        tenthash_state *state = tenthash_new();
+        for (size_t j = 0; j < inputs_len[i]; j++) {
+            tenthash_update(state, &inputs[i][j], 1);
+        }
+        tenthash_finalize(state, output);
+        tenthash_free(state);
+        failures += check("tenthash_state", i, output);
+
+        // Stack-allocated hasher, fed in two pieces.
+        tenthash_hasher hasher;
+        tenthash_hasher_init(&hasher);
+        tenthash_hasher_update(&hasher, inputs[i], inputs_len[i] / 2);
+        tenthash_hasher_update(&hasher, inputs[i] + inputs_len[i] / 2, inputs_len[i] - inputs_len[i] / 2);
+        tenthash_hasher_finalize(&hasher, output);
+        failures += check("tenthash_hasher", i, output);
+    }
+
+    // NULL is allowed for empty data.
+    tenthash_hash(NULL, 0, output);
+    failures += check("tenthash_hash with NULL", 0, output);
+
+    if (failures == 0) {
+        printf("All test vectors passed.\n");
+        return 0;
+    }
+    return 1;
+}
@@ -0,0 +1,141 @@
+use std::ptr;
+
+use tenthash::Digest;
+use tenthash_capi::*;
+
+/// The path of the shared test vector corpus, relative to this crate.
+const CORPUS_PATH: &str = "../test_vectors/corpus.txt";
+
+/// Reads and parses the corpus, returning `(message, digest)` pairs.  See
+/// `test_vectors/README.md` for the format.
+fn corpus() -> Vec<(Vec<u8>, String)> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(CORPUS_PATH);
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("couldn't read {}: {}", path.display(), e));
+
+    let mut lines = text
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    assert_eq!(lines.next(), Some("tenthash-test-vectors 1"));
+
+    lines
+        .map(|line| {
+            let (digest, message) = line.split_once(' ').unwrap();
+            let data = if let Some(hex) = message.strip_prefix("hex:") {
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..(i + 2)], 16).unwrap())
+                    .collect()
+            } else if let Some(len) = message.strip_prefix("pattern:") {
+                let len: usize = len.parse().unwrap();
+                (0..len).map(|i| (i % 251) as u8).collect()
+            } else {
+                panic!("unknown message in corpus: {}", message);
+            };
+            (data, digest.to_string())
+        })
+        .collect()
+}
+
+fn to_hex(digest: [u8; 20]) -> String {
+    Digest::from(digest).to_string()
+}
+
+/// A corpus message that spans several blocks, and its digest.
+fn long_test_vector() -> (Vec<u8>, String) {
+    corpus()
+        .into_iter()
+        .find(|(data, _)| data.len() == 1000)
+        .unwrap()
+}
+
+#[test]
+fn one_shot() {
+    for (input, digest) in corpus() {
+        let mut out = [0u8; TENTHASH_DIGEST_SIZE];
+        unsafe { tenthash_hash(input.as_ptr(), input.len(), out.as_mut_ptr()) };
+        assert_eq!(to_hex(out), digest, "{} bytes", input.len());
+    }
+}
+
+#[test]
+fn null_data_with_zero_length() {
+    let empty_digest = to_hex(tenthash::hash([]));
+
+    let mut out = [0u8; TENTHASH_DIGEST_SIZE];
+    unsafe { tenthash_hash(ptr::null(), 0, out.as_mut_ptr()) };
+    assert_eq!(to_hex(out), empty_digest);
+
+    let mut out = [0u8; TENTHASH_DIGEST_SIZE];
+    unsafe {
+        let state = tenthash_new();
+        tenthash_update(state, ptr::null(), 0);
+        tenthash_finalize(state, out.as_mut_ptr());
+        tenthash_free(state);
+    }
+    assert_eq!(to_hex(out), empty_digest);
+
+    let mut out = [0u8; TENTHASH_DIGEST_SIZE];
+    unsafe {
+        let mut hasher = std::mem::MaybeUninit::<TentHashHasher>::uninit();
+        tenthash_hasher_init(hasher.as_mut_ptr());
+        tenthash_hasher_update(hasher.as_mut_ptr(), ptr::null(), 0);
+        tenthash_hasher_finalize(hasher.as_ptr(), out.as_mut_ptr());
+    }
+    assert_eq!(to_hex(out), empty_digest);
+}
+
+#[test]
+fn heap_state() {
+    let (data, digest) = long_test_vector();
+    for piece_size in [1, 7, 32, 33, 1000] {
+        let mut out = [0u8; TENTHASH_DIGEST_SIZE];
+        unsafe {
+            let state = tenthash_new();
+            for piece in data.chunks(piece_size) {
+                tenthash_update(state, piece.as_ptr(), piece.len());
+            }
+            tenthash_finalize(state, out.as_mut_ptr());
+            tenthash_free(state);
+        }
+        assert_eq!(to_hex(out), digest, "piece size {}", piece_size);
+    }
+}
+
+#[test]
+fn stack_hasher() {
+    let (data, digest) = long_test_vector();
+    for piece_size in [1, 7, 32, 33, 1000] {
+        let mut out = [0u8; TENTHASH_DIGEST_SIZE];
+        unsafe {
+            let mut hasher = std::mem::MaybeUninit::<TentHashHasher>::uninit();
+            tenthash_hasher_init(hasher.as_mut_ptr());
+            for piece in data.chunks(piece_size) {
+                tenthash_hasher_update(hasher.as_mut_ptr(), piece.as_ptr(), piece.len());
+            }
+            tenthash_hasher_finalize(hasher.as_ptr(), out.as_mut_ptr());
+        }
+        assert_eq!(to_hex(out), digest, "piece size {}", piece_size);
+    }
+}
+
+#[test]
+fn finalize_is_non_destructive() {
+    let mut first = [0u8; TENTHASH_DIGEST_SIZE];
+    let mut second = [0u8; TENTHASH_DIGEST_SIZE];
+    unsafe {
+        let state = tenthash_new();
+        tenthash_update(state, b"Hello".as_ptr(), 5);
+        tenthash_finalize(state, first.as_mut_ptr());
+        tenthash_update(state, b" world!".as_ptr(), 7);
+        tenthash_finalize(state, second.as_mut_ptr());
+        tenthash_free(state);
+    }
+    assert_eq!(first, tenthash::hash("Hello"));
+    assert_eq!(second, tenthash::hash("Hello world!"));
+}
+
+#[test]
+fn free_null() {
+    unsafe { tenthash_free(ptr::null_mut()) };
+}
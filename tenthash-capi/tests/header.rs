@@ -0,0 +1,31 @@
+//! Makes sure that the checked-in header is up to date.
+//!
+//! Run with `TENTHASH_UPDATE_HEADER=1` to regenerate it.
+
+use std::path::Path;
+
+#[test]
+fn header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let header_path = crate_dir.join("include/tenthash_capi.h");
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+
+    if std::env::var_os("TENTHASH_UPDATE_HEADER").is_some() {
+        std::fs::write(&header_path, &generated).unwrap();
+    }
+
+    let checked_in = std::fs::read(&header_path).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "include/tenthash_capi.h is out of date.  Regenerate it by running the \
+         tests with TENTHASH_UPDATE_HEADER=1."
+    );
+}
@@ -0,0 +1,73 @@
+//! A deliberately broken implementation of the conformance checker's
+//! protocol, for testing the checker itself.
+//!
+//! The first argument selects the bug:
+//!
+//! - `ff`: gives the wrong digest for any message containing a 0xff byte.
+//! - `empty-update`: gives the wrong digest when streaming with any empty
+//!   update.
+//! - `crash`: exits when asked to hash a message longer than 1000 bytes.
+//! - `hang`: stops responding when asked to hash a message longer than
+//!   1000 bytes.
+//! - `garbage`: responds with something other than a digest for messages
+//!   longer than 1000 bytes.
+
+use std::io::{self, BufRead, Write};
+
+use tenthash::{Digest, TentHash};
+
+fn main() -> io::Result<()> {
+    let bug = std::env::args().nth(1).expect("no bug given");
+    let stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    let mut hasher = TentHash::new();
+    let mut message = Vec::new();
+    let mut had_empty_update = false;
+
+    for line in stdin.lines() {
+        let line = line?;
+        let (command, hex) = line.split_once(' ').unwrap_or((&line, ""));
+        let data: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..(i + 2)], 16).unwrap())
+            .collect();
+
+        let mut digest = match command {
+            "hash" => {
+                message = data;
+                tenthash::hash(&message)
+            }
+            "update" => {
+                had_empty_update |= data.is_empty();
+                hasher.update(&data);
+                message.extend_from_slice(&data);
+                continue;
+            }
+            "finalize" => std::mem::take(&mut hasher).finalize(),
+            _ => panic!("unknown command: {}", command),
+        };
+
+        match bug.as_str() {
+            "ff" if message.contains(&0xff) => digest[0] ^= 1,
+            "empty-update" if had_empty_update => digest[0] ^= 1,
+            "crash" if message.len() > 1000 => std::process::exit(1),
+            "hang" if message.len() > 1000 => loop {
+                std::thread::park();
+            },
+            "garbage" if message.len() > 1000 => {
+                writeln!(stdout, "what's a digest?")?;
+                stdout.flush()?;
+                message.clear();
+                continue;
+            }
+            _ => {}
+        }
+        message.clear();
+        had_empty_update = false;
+
+        writeln!(stdout, "{}", Digest::from(digest))?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
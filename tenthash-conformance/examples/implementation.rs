@@ -0,0 +1,54 @@
+//! A minimal implementation of the conformance checker's protocol, using
+//! the Rust tenthash crate.
+//!
+//! This is a starting point for adding the protocol to implementations in
+//! other languages.  To check it:
+//!
+//! ```text
+//! cargo build --example implementation
+//! cargo run -- target/debug/examples/implementation
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use tenthash::{Digest, TentHash};
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    let mut hasher = TentHash::new();
+
+    for line in stdin.lines() {
+        let line = line?;
+        let (command, hex) = line.split_once(' ').unwrap_or((&line, ""));
+        let data = decode_hex(hex).expect("invalid hex");
+
+        match command {
+            "hash" => {
+                writeln!(stdout, "{}", Digest::from(tenthash::hash(&data)))?;
+            }
+            "update" => hasher.update(&data),
+            "finalize" => {
+                let digest = std::mem::take(&mut hasher).finalize();
+                writeln!(stdout, "{}", Digest::from(digest))?;
+            }
+            _ => panic!("unknown command: {}", command),
+        }
+
+        // The checker waits for each digest before sending more requests,
+        // so it has to be flushed right away.
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..(i + 2))?, 16).ok())
+        .collect()
+}
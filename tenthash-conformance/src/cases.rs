@@ -0,0 +1,148 @@
+//! Test cases, and generating them.
+
+/// A message to hash, and how to pass it to the implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub message: Vec<u8>,
+
+    /// The lengths of the chunks to stream the message in, which always sum
+    /// to the message length.  `None` means hashing it in one shot.
+    pub chunk_lens: Option<Vec<usize>>,
+}
+
+impl Case {
+    pub fn one_shot(message: Vec<u8>) -> Case {
+        Case {
+            message,
+            chunk_lens: None,
+        }
+    }
+
+    pub fn streaming(message: Vec<u8>, chunk_lens: Vec<usize>) -> Case {
+        debug_assert_eq!(chunk_lens.iter().sum::<usize>(), message.len());
+        Case {
+            message,
+            chunk_lens: Some(chunk_lens),
+        }
+    }
+
+    /// Returns the chunks of the message, if it's streamed.
+    pub fn chunks(&self) -> Option<Vec<&[u8]>> {
+        let chunk_lens = self.chunk_lens.as_ref()?;
+        let mut rest = &self.message[..];
+        let mut chunks = Vec::with_capacity(chunk_lens.len());
+        for &len in chunk_lens {
+            let (chunk, tail) = rest.split_at(len);
+            chunks.push(chunk);
+            rest = tail;
+        }
+        Some(chunks)
+    }
+
+    /// Returns the expected digest, as computed by this crate.
+    pub fn expected(&self) -> tenthash::Digest {
+        tenthash::hash(&self.message).into()
+    }
+}
+
+/// Returns the edge case messages: all short lengths, block boundaries, and
+/// messages of all zero and all one bits.
+///
+/// When `streaming` is true, each message is also streamed in several ways.
+pub fn edge_cases(streaming: bool) -> Vec<Case> {
+    let mut messages = Vec::new();
+    for len in 0..=130 {
+        messages.push(pattern(len));
+    }
+    for n in 8..=20 {
+        for len in [(1 << n) - 1, 1 << n, (1 << n) + 1] {
+            messages.push(pattern(len));
+        }
+    }
+    for len in [1, 31, 32, 33, 64, 1000, 4096] {
+        messages.push(vec![0x00; len]);
+        messages.push(vec![0xff; len]);
+    }
+
+    let mut cases = Vec::new();
+    for message in messages {
+        if streaming {
+            let len = message.len();
+
+            // All at once, one byte at a time, and in whole blocks.
+            cases.push(Case::streaming(message.clone(), vec![len]));
+            if len <= 256 {
+                cases.push(Case::streaming(message.clone(), vec![1; len]));
+            }
+            let mut blocks = vec![32; len / 32];
+            blocks.push(len % 32);
+            cases.push(Case::streaming(message.clone(), blocks));
+
+            // With empty updates at the start and end.
+            cases.push(Case::streaming(message.clone(), vec![0, len, 0]));
+        }
+        cases.push(Case::one_shot(message));
+    }
+    cases
+}
+
+/// Returns a random message of up to `max_len` bytes, hashed in one shot or
+/// (if `streaming` is true) in random chunks.
+pub fn random_case(rng: &mut Rng, max_len: usize, streaming: bool) -> Case {
+    // Favor shorter messages, which are quicker and more likely to hit edge
+    // cases, but still test long ones.
+    let max_len = match rng.below(10) {
+        0..=4 => max_len.min(256),
+        5..=7 => max_len.min(4096),
+        _ => max_len,
+    };
+    let len = rng.below(max_len as u64 + 1) as usize;
+    let message: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+
+    if !streaming || rng.below(4) == 0 {
+        return Case::one_shot(message);
+    }
+
+    // Random chunk boundaries, which can coincide to make empty chunks.
+    let boundary_count = rng.below(16) as usize;
+    let mut boundaries: Vec<usize> = (0..boundary_count)
+        .map(|_| rng.below(len as u64 + 1) as usize)
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.push(len);
+
+    let mut chunk_lens = Vec::with_capacity(boundaries.len());
+    let mut start = 0;
+    for boundary in boundaries {
+        chunk_lens.push(boundary - start);
+        start = boundary;
+    }
+    Case::streaming(message, chunk_lens)
+}
+
+/// Returns a message of `len` bytes where byte `i` is `i % 251`.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// A small, seedable random number generator (SplitMix64).
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`.  The tiny bias doesn't matter here.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
@@ -0,0 +1,205 @@
+//! Running the implementation under test as a subprocess.
+//!
+//! See the readme for the protocol.  In short: requests are lines on the
+//! implementation's stdin, with data as hex, and each `hash` or `finalize`
+//! request gets a digest line on its stdout in response.
+
+use std::{
+    ffi::OsString,
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use tenthash::Digest;
+
+use crate::cases::Case;
+
+/// The implementation under test.
+///
+/// The subprocess is started on first use, and restarted after any error,
+/// since its state is unknown at that point.
+pub struct Implementation {
+    command: Vec<OsString>,
+    timeout: Duration,
+    process: Option<Process>,
+}
+
+/// A running subprocess.
+///
+/// Writing and reading happen on separate threads, so that a subprocess
+/// that stops responding (or reading) can't block us past the timeout.
+struct Process {
+    child: Child,
+    requests: Sender<Vec<u8>>,
+    responses: Receiver<io::Result<String>>,
+}
+
+impl Implementation {
+    pub fn new(command: Vec<OsString>, timeout: Duration) -> Implementation {
+        Implementation {
+            command,
+            timeout,
+            process: None,
+        }
+    }
+
+    /// Starts the subprocess, if it's not already running.
+    ///
+    /// This is done automatically by `digest()`, but calling it first
+    /// separates failing to launch the implementation from it misbehaving.
+    pub fn start(&mut self) -> io::Result<()> {
+        if self.process.is_some() {
+            return Ok(());
+        }
+
+        let mut child = Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let (requests, request_receiver) = mpsc::channel::<Vec<u8>>();
+        let mut stdin = child.stdin.take().unwrap();
+        thread::spawn(move || {
+            for request in request_receiver {
+                if stdin
+                    .write_all(&request)
+                    .and_then(|_| stdin.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let (response_sender, responses) = mpsc::channel();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if response_sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.process = Some(Process {
+            child,
+            requests,
+            responses,
+        });
+        Ok(())
+    }
+
+    /// Has the implementation compute the digest for a test case.
+    pub fn digest(&mut self, case: &Case) -> Result<Digest, Error> {
+        let result = self.try_digest(case);
+        if result.is_err() {
+            self.stop();
+        }
+        result
+    }
+
+    fn try_digest(&mut self, case: &Case) -> Result<Digest, Error> {
+        self.start().map_err(Error::Io)?;
+        let process = self.process.as_mut().unwrap();
+
+        let mut request = Vec::new();
+        match case.chunks() {
+            None => push_request(&mut request, "hash", &case.message),
+            Some(chunks) => {
+                for chunk in chunks {
+                    push_request(&mut request, "update", chunk);
+                }
+                request.extend_from_slice(b"finalize\n");
+            }
+        }
+        // If this fails the writer thread has exited, which means the
+        // subprocess has too.  Reading the response will tell.
+        let _ = process.requests.send(request);
+
+        let line = match process.responses.recv_timeout(self.timeout) {
+            Ok(Ok(line)) => line,
+            Ok(Err(e)) => return Err(Error::Io(e)),
+            Err(RecvTimeoutError::Timeout) => return Err(Error::Timeout(self.timeout)),
+            Err(RecvTimeoutError::Disconnected) => return Err(Error::Exited),
+        };
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+
+        match line.parse::<Digest>() {
+            Ok(digest) if line.len() == 40 => Ok(digest),
+            _ => Err(Error::InvalidResponse(line.into())),
+        }
+    }
+
+    /// Kills the subprocess, if it's running.
+    fn stop(&mut self) {
+        if let Some(mut process) = self.process.take() {
+            let _ = process.child.kill();
+            let _ = process.child.wait();
+        }
+    }
+}
+
+impl Drop for Implementation {
+    fn drop(&mut self) {
+        if let Some(mut process) = self.process.take() {
+            // Closing stdin is the signal to exit normally.
+            drop(process.requests);
+            if !matches!(process.child.try_wait(), Ok(Some(_))) {
+                thread::sleep(Duration::from_millis(100));
+                let _ = process.child.kill();
+            }
+            let _ = process.child.wait();
+        }
+    }
+}
+
+/// Appends a request line, with `data` as hex, to `request`.
+fn push_request(request: &mut Vec<u8>, command: &str, data: &[u8]) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    request.extend_from_slice(command.as_bytes());
+    if !data.is_empty() {
+        request.push(b' ');
+        for &byte in data {
+            request.push(HEX[(byte >> 4) as usize]);
+            request.push(HEX[(byte & 0xf) as usize]);
+        }
+    }
+    request.push(b'\n');
+}
+
+/// The ways the implementation can fail, other than giving wrong digests.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Timeout(Duration),
+    Exited,
+    InvalidResponse(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Timeout(timeout) => {
+                write!(f, "no response within {} seconds", timeout.as_secs_f64())
+            }
+            Error::Exited => f.write_str("exited without responding"),
+            Error::InvalidResponse(line) => {
+                const MAX_LEN: usize = 80;
+                if line.len() > MAX_LEN {
+                    let end = (0..=MAX_LEN).rev().find(|&i| line.is_char_boundary(i));
+                    write!(f, "invalid response: {:?}...", &line[..end.unwrap()])
+                } else {
+                    write!(f, "invalid response: {:?}", line)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Checks a third-party TentHash implementation for conformance.
+//!
+//! The implementation under test is run as a subprocess, and hashes messages
+//! sent to it over a simple line-based protocol on stdin and stdout (see the
+//! readme).  Its digests are compared against this crate's, first for a set
+//! of edge cases and then for random messages.  When a digest doesn't match,
+//! the failing input is minimized and printed.
+
+mod cases;
+mod implementation;
+mod minimize;
+
+use std::{
+    ffi::OsString,
+    io::{self, Write},
+    process::ExitCode,
+    time::Duration,
+};
+
+use cases::{Case, Rng};
+use implementation::Implementation;
+
+const NAME: &str = "tenthash-conformance";
+
+const USAGE: &str = "\
+Usage: tenthash-conformance [OPTION]... COMMAND [ARG]...
+Check a TentHash implementation for conformance.
+
+Runs COMMAND with the given ARGs, and has it hash edge case and random
+messages using the line-based protocol described in the readme.  Its digests
+are compared against those of the Rust tenthash crate.  If any differ, the
+failing input is minimized and printed.
+
+  -n, --count N        number of random messages to check (default 1000)
+  -s, --seed N         random seed, for reproducing a run (default: random)
+      --max-len N      maximum length of random messages, in bytes
+                         (default 65536)
+      --no-streaming   only hash messages in one shot, for implementations
+                         that don't support streaming
+      --timeout SECS   how long to wait for each response (default 10)
+
+      --help           display this help and exit
+      --version        output version information and exit
+
+The exit code is 0 if all digests matched, 1 if any didn't or the
+implementation misbehaved, and 2 if it couldn't be run at all.
+";
+
+#[derive(Debug)]
+struct Options {
+    count: u64,
+    seed: Option<u64>,
+    max_len: usize,
+    streaming: bool,
+    timeout: Duration,
+    command: Vec<OsString>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            count: 1000,
+            seed: None,
+            max_len: 1 << 16,
+            streaming: true,
+            timeout: Duration::from_secs(10),
+            command: Vec::new(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args_os().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}: {}", NAME, message);
+            eprintln!("Try '{} --help' for more information.", NAME);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut implementation = Implementation::new(options.command.clone(), options.timeout);
+    if let Err(e) = implementation.start() {
+        eprintln!(
+            "{}: couldn't run {}: {}",
+            NAME,
+            options.command[0].to_string_lossy(),
+            e
+        );
+        return ExitCode::from(2);
+    }
+
+    match run(&options, &mut implementation) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            if e.kind() != io::ErrorKind::BrokenPipe {
+                eprintln!("{}: write error: {}", NAME, e);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the command line arguments.
+///
+/// Returns `Ok(None)` if the program should exit successfully without doing
+/// anything else (e.g. after printing `--help`).
+fn parse_args(mut args: impl Iterator<Item = OsString>) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        let arg_str = arg.to_string_lossy();
+        if arg_str == "--" {
+            options.command.extend(args.by_ref());
+            break;
+        }
+        if !arg_str.starts_with('-') || arg_str == "-" {
+            options.command.push(arg);
+            options.command.extend(args.by_ref());
+            break;
+        }
+
+        let mut value = |name: &str| -> Result<String, String> {
+            args.next()
+                .map(|value| value.to_string_lossy().into_owned())
+                .ok_or_else(|| format!("option '{}' requires an argument", name))
+        };
+        let invalid =
+            |name: &str, value: &str| format!("invalid value for '{}': '{}'", name, value);
+
+        match &*arg_str {
+            "-n" | "--count" => {
+                let v = value(&arg_str)?;
+                options.count = v.parse().map_err(|_| invalid(&arg_str, &v))?;
+            }
+            "-s" | "--seed" => {
+                let v = value(&arg_str)?;
+                options.seed = Some(v.parse().map_err(|_| invalid(&arg_str, &v))?);
+            }
+            "--max-len" => {
+                let v = value(&arg_str)?;
+                options.max_len = v.parse().map_err(|_| invalid(&arg_str, &v))?;
+            }
+            "--timeout" => {
+                let v = value(&arg_str)?;
+                options.timeout = v
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| invalid(&arg_str, &v))?;
+            }
+            "--no-streaming" => options.streaming = false,
+            "--help" => {
+                print!("{}", USAGE);
+                return Ok(None);
+            }
+            "--version" => {
+                println!("{} {}", NAME, env!("CARGO_PKG_VERSION"));
+                return Ok(None);
+            }
+            _ => return Err(format!("unrecognized option '{}'", arg_str)),
+        }
+    }
+
+    if options.command.is_empty() {
+        return Err("missing implementation command".into());
+    }
+
+    Ok(Some(options))
+}
+
+/// Checks the implementation against the edge cases and then random
+/// messages, stopping at the first failure.
+///
+/// Returns whether all checks passed.
+fn run(options: &Options, implementation: &mut Implementation) -> io::Result<bool> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    let seed = options.seed.unwrap_or_else(random_seed);
+    writeln!(out, "Seed: {}", seed)?;
+
+    let edge_cases = cases::edge_cases(options.streaming);
+    let mut checked = 0u64;
+    for case in &edge_cases {
+        if let Some(failure) = check(implementation, case) {
+            report(&mut out, implementation, case.clone(), &failure, seed)?;
+            return Ok(false);
+        }
+        checked += 1;
+    }
+    writeln!(out, "Passed {} edge cases.", checked)?;
+
+    let mut rng = Rng::new(seed);
+    for _ in 0..options.count {
+        let case = cases::random_case(&mut rng, options.max_len, options.streaming);
+        if let Some(failure) = check(implementation, &case) {
+            report(&mut out, implementation, case, &failure, seed)?;
+            return Ok(false);
+        }
+    }
+    writeln!(out, "Passed {} random cases.", options.count)?;
+
+    Ok(true)
+}
+
+/// Checks a single case, and returns a description of the failure if it
+/// fails.
+fn check(implementation: &mut Implementation, case: &Case) -> Option<String> {
+    match implementation.digest(case) {
+        Ok(digest) if digest == case.expected() => None,
+        Ok(digest) => Some(digest.to_string()),
+        Err(e) => Some(format!("error: {}", e)),
+    }
+}
+
+/// Minimizes a failing case and prints it.
+fn report(
+    out: &mut impl Write,
+    implementation: &mut Implementation,
+    case: Case,
+    failure: &str,
+    seed: u64,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "FAILED: {} byte message, expected {}, got {}",
+        case.message.len(),
+        case.expected(),
+        failure
+    )?;
+    writeln!(out, "Minimizing...")?;
+    out.flush()?;
+
+    let case = minimize::minimize(case, |candidate| check(implementation, candidate).is_some());
+    // Check once more for the output, since the minimized case may fail
+    // differently than the original did.
+    let failure = check(implementation, &case).unwrap_or_else(|| "(flaky failure)".into());
+
+    writeln!(out)?;
+    writeln!(out, "Minimized failing input:")?;
+    match &case.chunk_lens {
+        None => writeln!(out, "  hashed:   in one shot")?,
+        Some(chunk_lens) => writeln!(out, "  hashed:   in chunks of {:?} bytes", chunk_lens)?,
+    }
+    let message = &case.message;
+    let plural = if message.len() == 1 { "" } else { "s" };
+    write!(out, "  message:  {} byte{}", message.len(), plural)?;
+    if message.len() > 1 && message.iter().all(|&byte| byte == message[0]) {
+        write!(out, ", all {:02x}", message[0])?;
+    } else if !message.is_empty() {
+        write!(out, ", hex:")?;
+        for (i, byte) in message.iter().enumerate() {
+            if i % 32 == 0 {
+                write!(out, "\n    ")?;
+            }
+            write!(out, "{:02x}", byte)?;
+        }
+    }
+    writeln!(out)?;
+    writeln!(out, "  expected: {}", case.expected())?;
+    writeln!(out, "  got:      {}", failure)?;
+    writeln!(out)?;
+    writeln!(out, "Reproduce with '--seed {}'.", seed)?;
+
+    Ok(())
+}
+
+/// Returns a seed that's different on every run.
+fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    // Standard library `RandomState`s are randomly keyed per process.
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0),
+    );
+    hasher.finish()
+}
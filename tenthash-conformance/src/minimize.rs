@@ -0,0 +1,152 @@
+//! Shrinking failing test cases, to make failures easier to diagnose.
+
+use crate::cases::Case;
+
+/// The maximum number of candidate cases to try.  Each one is a round trip
+/// to the implementation, so this keeps minimization from taking forever
+/// on slow implementations or long messages.
+const MAX_ATTEMPTS: usize = 20_000;
+
+/// Shrinks a failing case, by repeatedly simplifying it for as long as
+/// `fails` still returns true for the result.
+///
+/// The simplifications, roughly in order of priority, are: hashing in one
+/// shot instead of streaming, streaming in fewer chunks, removing bytes from
+/// the message, and replacing bytes with zeros.
+pub fn minimize(case: Case, mut fails: impl FnMut(&Case) -> bool) -> Case {
+    let mut minimizer = Minimizer {
+        case,
+        attempts: 0,
+        fails: &mut fails,
+    };
+    while minimizer.attempts < MAX_ATTEMPTS {
+        let progress = minimizer.make_one_shot()
+            || minimizer.merge_chunks()
+            || minimizer.remove_bytes()
+            || minimizer.zero_bytes();
+        if !progress {
+            break;
+        }
+    }
+    minimizer.case
+}
+
+struct Minimizer<'a> {
+    case: Case,
+    attempts: usize,
+    fails: &'a mut dyn FnMut(&Case) -> bool,
+}
+
+impl Minimizer<'_> {
+    /// Replaces the current case with `candidate` if it still fails, and
+    /// returns whether it did.
+    fn try_candidate(&mut self, candidate: Case) -> bool {
+        if self.attempts >= MAX_ATTEMPTS || candidate == self.case {
+            return false;
+        }
+        self.attempts += 1;
+        if (self.fails)(&candidate) {
+            self.case = candidate;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn make_one_shot(&mut self) -> bool {
+        self.case.chunk_lens.is_some()
+            && self.try_candidate(Case::one_shot(self.case.message.clone()))
+    }
+
+    /// Tries removing each empty chunk, and merging each pair of adjacent
+    /// chunks.
+    fn merge_chunks(&mut self) -> bool {
+        let mut progress = false;
+        let mut i = 0;
+        while let Some(chunk_lens) = &self.case.chunk_lens {
+            if i >= chunk_lens.len() {
+                break;
+            }
+            let mut candidate_lens = chunk_lens.clone();
+            if candidate_lens[i] == 0 {
+                candidate_lens.remove(i);
+            } else if i + 1 < candidate_lens.len() {
+                candidate_lens[i] += candidate_lens.remove(i + 1);
+            } else {
+                i += 1;
+                continue;
+            }
+
+            let candidate = Case::streaming(self.case.message.clone(), candidate_lens);
+            if self.try_candidate(candidate) {
+                progress = true;
+            } else {
+                i += 1;
+            }
+        }
+        progress
+    }
+
+    /// Tries removing ranges of bytes from the message, from large ranges
+    /// down to single bytes.
+    fn remove_bytes(&mut self) -> bool {
+        let mut progress = false;
+        let mut size = self.case.message.len().div_ceil(2);
+        while size > 0 {
+            let mut start = 0;
+            while start < self.case.message.len() {
+                let end = (start + size).min(self.case.message.len());
+                if self.try_candidate(remove_range(&self.case, start, end)) {
+                    progress = true;
+                } else {
+                    start += size;
+                }
+            }
+            size /= 2;
+        }
+        progress
+    }
+
+    /// Tries replacing ranges of bytes with zeros, from large ranges down to
+    /// single bytes.
+    fn zero_bytes(&mut self) -> bool {
+        let mut progress = false;
+        let mut size = self.case.message.len();
+        while size > 0 {
+            for start in (0..self.case.message.len()).step_by(size) {
+                let end = (start + size).min(self.case.message.len());
+                if self.case.message[start..end].iter().any(|&byte| byte != 0) {
+                    let mut candidate = self.case.clone();
+                    candidate.message[start..end].fill(0);
+                    progress |= self.try_candidate(candidate);
+                }
+            }
+            size /= 2;
+        }
+        progress
+    }
+}
+
+/// Returns `case` with the bytes in `start..end` removed from its message,
+/// shrinking its chunks to match.
+fn remove_range(case: &Case, start: usize, end: usize) -> Case {
+    let mut message = case.message.clone();
+    message.drain(start..end);
+
+    match &case.chunk_lens {
+        None => Case::one_shot(message),
+        Some(chunk_lens) => {
+            let mut chunk_start = 0;
+            let chunk_lens = chunk_lens
+                .iter()
+                .map(|&len| {
+                    let chunk_end = chunk_start + len;
+                    let overlap = chunk_end.min(end).saturating_sub(chunk_start.max(start));
+                    chunk_start = chunk_end;
+                    len - overlap
+                })
+                .collect();
+            Case::streaming(message, chunk_lens)
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Returns the path of one of the example implementations, which `cargo
+/// test` builds alongside the binary.
+fn example(name: &str) -> PathBuf {
+    let bin = PathBuf::from(env!("CARGO_BIN_EXE_tenthash-conformance"));
+    bin.parent()
+        .unwrap()
+        .join("examples")
+        .join(format!("{}{}", name, std::env::consts::EXE_SUFFIX))
+}
+
+fn run(args: &[&str], implementation: &str, implementation_args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_tenthash-conformance"))
+        .args(args)
+        .arg(example(implementation))
+        .args(implementation_args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn conforming() {
+    let output = run(&["--count", "200", "--seed", "5"], "implementation", &[]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert!(stdout(&output).starts_with("Seed: 5\n"));
+    assert!(stdout(&output).contains("edge cases.\n"));
+    assert!(stdout(&output).ends_with("Passed 200 random cases.\n"));
+}
+
+#[test]
+fn wrong_digest_is_minimized() {
+    let output = run(&["--seed", "5"], "faulty", &["ff"]);
+    assert_eq!(output.status.code(), Some(1));
+    let out = stdout(&output);
+    assert!(
+        out.contains(
+            "Minimized failing input:\n  \
+             hashed:   in one shot\n  \
+             message:  1 byte, hex:\n    ff\n  \
+             expected: 7ed6fb764f4cd1b3c574e2dc3883a8e583c43c58\n  \
+             got:      7fd6fb764f4cd1b3c574e2dc3883a8e583c43c58\n"
+        ),
+        "{}",
+        out
+    );
+    assert!(out.ends_with("Reproduce with '--seed 5'.\n"));
+}
+
+#[test]
+fn streaming() {
+    let output = run(&[], "faulty", &["empty-update"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("  hashed:   in chunks of [0] bytes\n  message:  0 bytes\n"));
+
+    // The bug doesn't affect hashing in one shot.
+    let output = run(
+        &["--no-streaming", "-n", "100"],
+        "faulty",
+        &["empty-update"],
+    );
+    assert!(output.status.success(), "{}", stdout(&output));
+}
+
+#[test]
+fn crash() {
+    let output = run(&[], "faulty", &["crash"]);
+    assert_eq!(output.status.code(), Some(1));
+    let out = stdout(&output);
+    assert!(out.contains("  message:  1001 bytes, all 00\n"), "{}", out);
+    assert!(out.contains("  got:      error: exited without responding\n"));
+}
+
+#[test]
+fn hang() {
+    let output = run(&["--timeout", "0.2"], "faulty", &["hang"]);
+    assert_eq!(output.status.code(), Some(1));
+    let out = stdout(&output);
+    assert!(out.contains("  message:  1001 bytes, all 00\n"), "{}", out);
+    assert!(out.contains("  got:      error: no response within 0.2 seconds\n"));
+}
+
+#[test]
+fn invalid_response() {
+    let output = run(&[], "faulty", &["garbage"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("  got:      error: invalid response: \"what's a digest?\"\n"));
+}
+
+#[test]
+fn usage_errors() {
+    let conformance = env!("CARGO_BIN_EXE_tenthash-conformance");
+
+    let output = Command::new(conformance).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("missing implementation command"));
+
+    let output = Command::new(conformance)
+        .args(["--count", "many", "foo"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("invalid value for '--count': 'many'"));
+
+    let output = Command::new(conformance)
+        .arg("./this/does/not/exist")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("couldn't run ./this/does/not/exist"));
+
+    let output = Command::new(conformance).arg("--help").output().unwrap();
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Usage: tenthash-conformance"));
+}
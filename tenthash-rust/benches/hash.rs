@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion, Throughput};
-use tenthash::{hash, TentHash};
+use tenthash::{hash, hash_many_into, tree, TentHash};
 
 //----
 
@@ -64,7 +64,125 @@ fn tent_hash_streaming(c: &mut Criterion) {
     }
 }
 
+// Note: batched hashing is only hardware accelerated with the `std` feature,
+// so run with `cargo bench --features std` for meaningful results.
+fn tent_hash_many(c: &mut Criterion) {
+    let benches = [
+        ("10b_messages", 10),     // 10-byte messages.
+        ("100b_messages", 100),   // 100-byte messages.
+        ("1kb_messages", 1000),   // 1-kilobyte messages.
+        ("10kb_messages", 10000), // 10-kilobyte messages.
+    ];
+
+    let mut group = c.benchmark_group("tent_hash_many");
+
+    for (name, message_size) in benches.iter() {
+        let message_count = 100000 / message_size;
+        let messages: Vec<Vec<u8>> = (0..message_count)
+            .map(|i| {
+                b"abcdefghijklmnopqrstuvwxyz"
+                    .iter()
+                    .copied()
+                    .cycle()
+                    .skip(i % 26)
+                    .take(*message_size)
+                    .collect()
+            })
+            .collect();
+        let mut digests = vec![[0u8; 20]; message_count];
+        group.throughput(Throughput::Bytes((message_count * message_size) as u64));
+
+        group.bench_function(format!("{}_one_at_a_time", name), |bench| {
+            bench.iter(|| {
+                for (message, digest) in messages.iter().zip(digests.iter_mut()) {
+                    *digest = hash(message);
+                }
+            })
+        });
+
+        group.bench_function(format!("{}_batched", name), |bench| {
+            bench.iter(|| {
+                hash_many_into(&messages, &mut digests);
+            })
+        });
+    }
+}
+
+fn tent_hash_tree(c: &mut Criterion) {
+    let data_size = 16 * 1024 * 1024;
+    let data: Vec<u8> = b"abcdefghijklmnopqrstuvwxyz"
+        .iter()
+        .copied()
+        .cycle()
+        .take(data_size)
+        .collect();
+
+    let mut group = c.benchmark_group("tent_hash_tree");
+    group.throughput(Throughput::Bytes(data_size as u64));
+
+    group.bench_function("16mb_plain", |bench| {
+        bench.iter(|| {
+            let _ = hash(&data);
+        })
+    });
+
+    group.bench_function("16mb_tree", |bench| {
+        bench.iter(|| {
+            let _ = tree::hash(&data);
+        })
+    });
+
+    #[cfg(feature = "rayon")]
+    group.bench_function("16mb_tree_parallel", |bench| {
+        bench.iter(|| {
+            let _ = tree::hash_parallel(&data);
+        })
+    });
+}
+
+#[cfg(feature = "mmap")]
+fn tent_hash_file(c: &mut Criterion) {
+    let data_size = 64 * 1024 * 1024;
+    let data: Vec<u8> = b"abcdefghijklmnopqrstuvwxyz"
+        .iter()
+        .copied()
+        .cycle()
+        .take(data_size)
+        .collect();
+    let path = std::env::temp_dir().join(format!("tenthash_bench_{}", std::process::id()));
+    std::fs::write(&path, &data).unwrap();
+
+    let mut group = c.benchmark_group("tent_hash_file");
+    group.throughput(Throughput::Bytes(data_size as u64));
+
+    group.bench_function("64mb_buffered", |bench| {
+        bench.iter(|| {
+            let _ = tenthash::hash_file(&path).unwrap();
+        })
+    });
+
+    group.bench_function("64mb_mmap", |bench| {
+        bench.iter(|| {
+            // SAFETY: nothing truncates the file during the benchmark.
+            let _ = unsafe { tenthash::hash_file_mmap(&path) }.unwrap();
+        })
+    });
+
+    group.finish();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(not(feature = "mmap"))]
+fn tent_hash_file(_: &mut Criterion) {}
+
 //----
 
-criterion_group!(benches, tent_hash_single_call, tent_hash_streaming);
+criterion_group!(
+    benches,
+    tent_hash_single_call,
+    tent_hash_streaming,
+    tent_hash_many,
+    tent_hash_tree,
+    tent_hash_file
+);
 criterion_main!(benches);
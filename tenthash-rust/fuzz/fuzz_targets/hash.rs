@@ -0,0 +1,25 @@
+//! Checks that `hash()` and the multi-message functions agree with the
+//! reference implementation on arbitrary data.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let expected = tenthash_reference::hash(data);
+
+    assert_eq!(tenthash::hash(data), expected);
+    assert_eq!(tenthash::const_hash(data), expected);
+
+    // Messages of different lengths in the same batch, so that lanes
+    // finish at different times.
+    let messages = [
+        data,
+        &data[..data.len() / 2],
+        &data[data.len() / 3..],
+        &data[..data.len().saturating_sub(1)],
+    ];
+    for (message, digest) in messages.iter().zip(tenthash::hash_4x(messages)) {
+        assert_eq!(digest, tenthash_reference::hash(message));
+    }
+});
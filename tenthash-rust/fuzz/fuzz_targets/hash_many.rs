@@ -0,0 +1,24 @@
+//! Checks that the multi-message functions agree with the reference
+//! implementation on arbitrary batches of messages.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|messages: Vec<Vec<u8>>| {
+    let expected: Vec<[u8; 20]> = messages
+        .iter()
+        .map(|m| tenthash_reference::hash(m))
+        .collect();
+
+    assert_eq!(tenthash::hash_many(&messages), expected);
+
+    let mut digests = vec![[0u8; 20]; messages.len()];
+    tenthash::hash_many_into(&messages, &mut digests);
+    assert_eq!(digests, expected);
+
+    for (group, expected) in messages.chunks_exact(8).zip(expected.chunks_exact(8)) {
+        let group: [&[u8]; 8] = core::array::from_fn(|i| &group[i][..]);
+        assert_eq!(tenthash::hash_8x(group), expected);
+    }
+});
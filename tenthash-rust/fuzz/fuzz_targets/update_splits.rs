@@ -0,0 +1,29 @@
+//! Checks that feeding data to `TentHash::update()` in arbitrary pieces
+//! gives the same digest as the reference implementation.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tenthash::TentHash;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    data: Vec<u8>,
+    // The lengths of the pieces, wrapped to the remaining data.  Whatever
+    // is left over at the end is passed in one last update.
+    splits: Vec<u16>,
+}
+
+fuzz_target!(|input: Input| {
+    let mut hasher = TentHash::new();
+    let mut rest = &input.data[..];
+    for &split in &input.splits {
+        let len = split as usize % (rest.len() + 1);
+        hasher.update(&rest[..len]);
+        rest = &rest[len..];
+    }
+    hasher.update(rest);
+
+    assert_eq!(hasher.finalize(), tenthash_reference::hash(&input.data));
+});
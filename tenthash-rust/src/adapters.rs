@@ -0,0 +1,286 @@
+//! Reader and writer adapters that hash the data passing through them,
+//! available with the `std` feature.
+//!
+//! Besides `std::io`, the adapters also implement the async I/O traits of
+//! tokio and futures-io when the `tokio` and `futures-io` features are
+//! enabled, respectively.
+
+use std::io::{self, IoSlice, Read, Write};
+
+use crate::{TentHash, DIGEST_SIZE};
+
+/// A reader adapter that hashes all data read through it.
+///
+/// Implements [`Read`] when `R` does, as well as tokio's `AsyncRead` and
+/// futures-io's `AsyncRead` when the `tokio` and `futures-io` features are
+/// enabled.  The async implementations require `R` to be [`Unpin`], so
+/// readers that aren't should be pinned first with e.g. `Box::pin()`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Read;
+/// use tenthash::HashingReader;
+///
+/// let mut reader = HashingReader::new(&b"Hello world!"[..]);
+/// let mut contents = String::new();
+/// reader.read_to_string(&mut contents).unwrap();
+///
+/// assert_eq!(reader.digest(), tenthash::hash("Hello world!"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: TentHash,
+}
+
+impl<R> HashingReader<R> {
+    pub fn new(inner: R) -> HashingReader<R> {
+        HashingReader {
+            inner,
+            hasher: TentHash::new(),
+        }
+    }
+
+    /// Returns the digest of all data read so far.
+    ///
+    /// Call this after reaching EOF to get the digest of all of the data.
+    pub fn digest(&self) -> [u8; DIGEST_SIZE] {
+        self.hasher.finalize()
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped reader.
+    ///
+    /// Data read directly from the wrapped reader isn't hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps the reader, discarding the hash.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// A writer adapter that hashes all data written through it.
+///
+/// Only data that the wrapped writer actually accepts is hashed, so after a
+/// successful flush the digest covers exactly the data that was written.
+///
+/// Implements [`Write`] when `W` does, as well as tokio's `AsyncWrite` and
+/// futures-io's `AsyncWrite` when the `tokio` and `futures-io` features are
+/// enabled.  The async implementations require `W` to be [`Unpin`], so
+/// writers that aren't should be pinned first with e.g. `Box::pin()`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+/// use tenthash::HashingWriter;
+///
+/// let mut writer = HashingWriter::new(Vec::new());
+/// writer.write_all(b"Hello world!").unwrap();
+///
+/// assert_eq!(writer.digest(), tenthash::hash("Hello world!"));
+/// assert_eq!(writer.get_ref(), b"Hello world!");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashingWriter<W> {
+    inner: W,
+    hasher: TentHash,
+}
+
+impl<W> HashingWriter<W> {
+    pub fn new(inner: W) -> HashingWriter<W> {
+        HashingWriter {
+            inner,
+            hasher: TentHash::new(),
+        }
+    }
+
+    /// Returns the digest of all data written so far.
+    pub fn digest(&self) -> [u8; DIGEST_SIZE] {
+        self.hasher.finalize()
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped writer.
+    ///
+    /// Data written directly to the wrapped writer isn't hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps the writer, discarding the hash.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let n = self.inner.write_vectored(bufs)?;
+        hash_written_slices(&mut self.hasher, bufs, n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Hashes the first `n` bytes of `bufs`.
+fn hash_written_slices(hasher: &mut TentHash, bufs: &[IoSlice<'_>], mut n: usize) {
+    for buf in bufs {
+        let len = buf.len().min(n);
+        hasher.update(&buf[..len]);
+        n -= len;
+        if n == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impls {
+    use core::{
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+    use std::io::{self, IoSlice};
+
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    use super::{hash_written_slices, HashingReader, HashingWriter};
+
+    impl<R: AsyncRead + Unpin> AsyncRead for HashingReader<R> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            let filled = buf.filled().len();
+            ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+            this.hasher.update(&buf.filled()[filled..]);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<W: AsyncWrite + Unpin> AsyncWrite for HashingWriter<W> {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+            this.hasher.update(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_write_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[IoSlice<'_>],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            let n = ready!(Pin::new(&mut this.inner).poll_write_vectored(cx, bufs))?;
+            hash_written_slices(&mut this.hasher, bufs, n);
+            Poll::Ready(Ok(n))
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.inner.is_write_vectored()
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+        }
+    }
+}
+
+#[cfg(feature = "futures-io")]
+mod futures_io_impls {
+    use core::{
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+    use std::io::{self, IoSlice};
+
+    use futures_io::{AsyncRead, AsyncWrite};
+
+    use super::{hash_written_slices, HashingReader, HashingWriter};
+
+    impl<R: AsyncRead + Unpin> AsyncRead for HashingReader<R> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+            this.hasher.update(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl<W: AsyncWrite + Unpin> AsyncWrite for HashingWriter<W> {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+            this.hasher.update(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_write_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[IoSlice<'_>],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            let n = ready!(Pin::new(&mut this.inner).poll_write_vectored(cx, bufs))?;
+            hash_written_slices(&mut this.hasher, bufs, n);
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().inner).poll_close(cx)
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Content-defined chunking, with a TentHash digest for each chunk.
+//!
+//! Content-defined chunking splits data into variable-size chunks at
+//! positions determined by the data itself, rather than at fixed offsets.
+//! This means that inserting or deleting data only changes the chunks near
+//! the edit, and all other chunks (and their digests) stay the same even
+//! though their offsets shift.  Which makes it the basis of deduplicating
+//! storage and backup systems: store each chunk under its digest, and any
+//! chunk that's already stored doesn't need to be stored again.
+//!
+//! The [`Chunker`] here implements the FastCDC algorithm, using a gear
+//! rolling hash with normalized chunking.  Its chunk boundaries are fully
+//! specified (see below) and platform independent, so the same data and
+//! settings always give the same chunks.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tenthash::chunking::Chunker;
+//!
+//! let data = vec![42u8; 1 << 20];
+//!
+//! let chunker = Chunker::default();
+//! for chunk in chunker.chunks(&data) {
+//!     let chunk_data = &data[chunk.offset as usize..][..chunk.len];
+//!     assert_eq!(chunk.digest, tenthash::hash(chunk_data));
+//! }
+//! ```
+//!
+//! # Algorithm
+//!
+//! For a chunker with minimum, average, and maximum sizes `min`, `avg`, and
+//! `max`, with `avg = 2^bits`, the length of the next chunk at the start of
+//! some data is determined as follows:
+//!
+//! 1. If the data is at most `min` bytes long, it's all one chunk.
+//! 2. Otherwise, a 64-bit hash `h` starts at zero, and for each byte `b` at
+//!    index `i`, starting from `i = min`, is updated as
+//!    `h = (h << 1) + GEAR[b]` (with wrapping arithmetic).  The chunk ends
+//!    after byte `i` if the top `bits + 1` bits of `h` are all zero while
+//!    `i < avg` (i.e. for chunks of up to `avg` bytes), or if the top
+//!    `bits - 1` bits are all zero otherwise.
+//! 3. If no chunk end is found, the chunk ends after `max` bytes or at the
+//!    end of the data, whichever comes first.
+//!
+//! `GEAR[b]` is the first 8 bytes (as a little-endian `u64`) of the TentHash
+//! digest of the single byte `b`.
+
+use core::fmt;
+
+use crate::{const_hash, DIGEST_SIZE};
+
+/// The gear hash table.  See the module documentation.
+const GEAR: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let digest = const_hash(&[i as u8]);
+        table[i] = u64::from_le_bytes([
+            digest[0], digest[1], digest[2], digest[3], digest[4], digest[5], digest[6], digest[7],
+        ]);
+        i += 1;
+    }
+    table
+};
+
+/// A chunk of data found by a [`Chunker`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Chunk {
+    /// The offset of the chunk in the data, in bytes.
+    pub offset: u64,
+
+    /// The length of the chunk, in bytes.
+    pub len: usize,
+
+    /// The TentHash digest of the chunk's data.
+    pub digest: [u8; DIGEST_SIZE],
+}
+
+/// A content-defined chunker, with configurable chunk sizes.
+///
+/// See the [module documentation](self) for details.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Chunker {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    mask_small: u64,
+    mask_large: u64,
+}
+
+impl Chunker {
+    /// Creates a chunker with the given minimum, average, and maximum chunk
+    /// sizes, in bytes.
+    ///
+    /// Chunks are at least `min_size` and at most `max_size` bytes long,
+    /// except for the last chunk of the data, which can be shorter.  Their
+    /// lengths are typically close to `avg_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `avg_size` is a power of two from 64 to 2^62, and
+    /// `0 < min_size <= avg_size <= max_size`.
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Chunker {
+        // The masks need `bits + 1 < 64`.
+        assert!(
+            avg_size.is_power_of_two() && avg_size >= 64 && avg_size.ilog2() <= 62,
+            "average chunk size must be a power of two from 64 to 2^62"
+        );
+        assert!(
+            0 < min_size && min_size <= avg_size && avg_size <= max_size,
+            "chunk sizes must satisfy 0 < min <= avg <= max"
+        );
+
+        let bits = avg_size.ilog2();
+        Chunker {
+            min_size,
+            avg_size,
+            max_size,
+            mask_small: !(u64::MAX >> (bits + 1)),
+            mask_large: !(u64::MAX >> (bits - 1)),
+        }
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// Returns the average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.avg_size
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the length of the first chunk of `data`.
+    ///
+    /// `data` is assumed to be the rest of the data being chunked, so when
+    /// no chunk boundary is found within it, all of it is one chunk.  To
+    /// get the correct result for longer data, `data` must therefore contain
+    /// at least [`max_size()`](Chunker::max_size) bytes.
+    ///
+    /// Returns zero only for empty data.
+    pub fn next_boundary(&self, data: &[u8]) -> usize {
+        if data.len() <= self.min_size {
+            return data.len();
+        }
+        let end = data.len().min(self.max_size);
+        let normal = end.min(self.avg_size);
+
+        let mut h = 0u64;
+        let mut i = self.min_size;
+        while i < normal {
+            h = (h << 1).wrapping_add(GEAR[data[i] as usize]);
+            if h & self.mask_small == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+        while i < end {
+            h = (h << 1).wrapping_add(GEAR[data[i] as usize]);
+            if h & self.mask_large == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+        end
+    }
+
+    /// Returns an iterator over the chunks of `data`.
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> Chunks<'a> {
+        Chunks {
+            chunker: *self,
+            data,
+            offset: 0,
+        }
+    }
+
+    /// Returns an iterator over the chunks of the data read from `reader`.
+    ///
+    /// This gives exactly the same chunks as [`chunks()`](Chunker::chunks)
+    /// does for the same data, while only buffering at most about twice
+    /// `max_size()` bytes at a time.  Available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn chunks_reader<R: std::io::Read>(&self, reader: R) -> ReaderChunks<R> {
+        ReaderChunks {
+            chunker: *self,
+            reader,
+            buf: std::vec::Vec::new(),
+            start: 0,
+            offset: 0,
+            eof: false,
+        }
+    }
+}
+
+/// Uses minimum, average, and maximum chunk sizes of 16 KiB, 64 KiB, and
+/// 256 KiB.
+impl Default for Chunker {
+    fn default() -> Chunker {
+        Chunker::new(16 * 1024, 64 * 1024, 256 * 1024)
+    }
+}
+
+impl fmt::Debug for Chunker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunker")
+            .field("min_size", &self.min_size)
+            .field("avg_size", &self.avg_size)
+            .field("max_size", &self.max_size)
+            .finish()
+    }
+}
+
+/// An iterator over the chunks of a slice.
+///
+/// Created by [`Chunker::chunks()`].
+#[derive(Debug, Clone)]
+pub struct Chunks<'a> {
+    chunker: Chunker,
+    data: &'a [u8],
+    offset: u64,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        let len = self.chunker.next_boundary(self.data);
+        if len == 0 {
+            return None;
+        }
+        let chunk = Chunk {
+            offset: self.offset,
+            len,
+            digest: crate::hash(&self.data[..len]),
+        };
+        self.data = &self.data[len..];
+        self.offset += len as u64;
+        Some(chunk)
+    }
+}
+
+/// An iterator over the chunks of the data from a reader.
+///
+/// Created by [`Chunker::chunks_reader()`].  Yields an error if reading
+/// fails, after which the iteration shouldn't be continued.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ReaderChunks<R> {
+    chunker: Chunker,
+    reader: R,
+    buf: std::vec::Vec<u8>,
+    start: usize, // Where the unchunked data in `buf` starts.
+    offset: u64,
+    eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ReaderChunks<R> {
+    /// Reads until there are at least `max_size()` bytes of unchunked data
+    /// buffered, or the reader is exhausted.
+    fn fill_buf(&mut self) -> std::io::Result<()> {
+        let max_size = self.chunker.max_size;
+        if self.buf.len() - self.start >= max_size || self.eof {
+            return Ok(());
+        }
+
+        // Move the unchunked data to the front, but only once most of the
+        // buffer has been chunked.  That way each byte is moved at most about
+        // once, rather than up to `max_size` bytes being moved per chunk.
+        if self.start > self.buf.len() / 2 {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+
+        let target_len = self.start + max_size;
+        let mut len = self.buf.len();
+        self.buf.resize(target_len, 0);
+        while len < target_len {
+            match self.reader.read(&mut self.buf[len..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => len += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.buf.truncate(len);
+                    return Err(e);
+                }
+            }
+        }
+        self.buf.truncate(len);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Iterator for ReaderChunks<R> {
+    type Item = std::io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<std::io::Result<Chunk>> {
+        if let Err(e) = self.fill_buf() {
+            return Some(Err(e));
+        }
+
+        let data = &self.buf[self.start..];
+        let len = self.chunker.next_boundary(data);
+        if len == 0 {
+            return None;
+        }
+        let chunk = Chunk {
+            offset: self.offset,
+            len,
+            digest: crate::hash(&data[..len]),
+        };
+        self.start += len;
+        self.offset += len as u64;
+        Some(Ok(chunk))
+    }
+}
@@ -0,0 +1,98 @@
+//! Compile-time hashing.
+
+use crate::{BLOCK_SIZE, DIGEST_SIZE, INITIAL_STATE, ROTATIONS};
+
+/// Computes TentHash in one go for a contiguous slice of data, usable in
+/// `const` contexts.
+///
+/// This gives exactly the same digests as [`hash()`](crate::hash), and is
+/// meant for computing the hashes of static data at compile time, for
+/// example to embed them in lookup tables.  It can also be called at run
+/// time, but is typically a fair bit slower than `hash()` there.
+///
+/// Note that compile-time evaluation is slow, and the compiler limits how
+/// long it's allowed to take.  So this is best suited to hashing smallish
+/// data like names and identifiers.
+///
+/// # Example
+///
+/// ```rust
+/// const HELLO_HASH: [u8; 20] = tenthash::const_hash(b"Hello world!");
+///
+/// assert_eq!(HELLO_HASH, tenthash::hash("Hello world!"));
+/// ```
+pub const fn const_hash(data: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut state = INITIAL_STATE;
+
+    // Process full-size blocks.
+    let full_blocks_len = data.len() - (data.len() % BLOCK_SIZE);
+    let mut i = 0;
+    while i < full_blocks_len {
+        state = xor_block_into_state(state, data, i, BLOCK_SIZE);
+        state = mix_state(state);
+        i += BLOCK_SIZE;
+    }
+
+    // Process any remaining data if needed, padded with zeros.
+    if full_blocks_len < data.len() {
+        state = xor_block_into_state(state, data, i, data.len() - full_blocks_len);
+        state = mix_state(state);
+    }
+
+    // Incorporate the message length (in bits) and do the
+    // final mixing.
+    state[0] ^= data.len() as u64 * 8;
+    state = mix_state(state);
+    state = mix_state(state);
+
+    // Get the digest as a byte array and return it.
+    let mut digest = [0u8; DIGEST_SIZE];
+    let mut i = 0;
+    while i < DIGEST_SIZE {
+        digest[i] = (state[i / 8] >> ((i % 8) * 8)) as u8;
+        i += 1;
+    }
+    digest
+}
+
+// The helpers below take and return the state by value rather than through
+// `&mut`, since mutable references in `const fn`s need a newer Rust than the
+// rest of the crate does.
+
+/// Xors `len` bytes of `data` starting at `offset` into the hash state, as a
+/// zero-padded block.
+const fn xor_block_into_state(
+    mut state: [u64; 4],
+    data: &[u8],
+    offset: usize,
+    len: usize,
+) -> [u64; 4] {
+    let mut i = 0;
+    while i < len {
+        state[i / 8] ^= (data[offset + i] as u64) << ((i % 8) * 8);
+        i += 1;
+    }
+    state
+}
+
+/// A `const fn` copy of the crate's `mix_state()`.
+///
+/// This is kept separate so that the run-time mixing function, which is on
+/// the hot path of everything else, doesn't have to be written to the limits
+/// of `const fn`.
+const fn mix_state(mut state: [u64; 4]) -> [u64; 4] {
+    let mut i = 0;
+    while i < ROTATIONS.len() {
+        let rot_pair = ROTATIONS[i];
+        state[0] = state[0].wrapping_add(state[2]);
+        state[2] = state[2].rotate_left(rot_pair[0]) ^ state[0];
+        state[1] = state[1].wrapping_add(state[3]);
+        state[3] = state[3].rotate_left(rot_pair[1]) ^ state[1];
+
+        let tmp = state[0];
+        state[0] = state[1];
+        state[1] = tmp;
+        i += 1;
+    }
+    state
+}
@@ -0,0 +1,47 @@
+//! RustCrypto `digest` trait implementations, available with the `digest`
+//! feature.
+//!
+//! Note that, despite implementing these traits, TentHash is *not* a
+//! cryptographic hash.  These implementations are provided purely for
+//! interoperability with code that is generic over hash functions.
+
+use digest::{
+    consts::U20, FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+};
+
+use crate::TentHash;
+
+impl HashMarker for TentHash {}
+
+impl OutputSizeUser for TentHash {
+    type OutputSize = U20;
+}
+
+impl Update for TentHash {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        TentHash::update(self, data);
+    }
+}
+
+impl FixedOutput for TentHash {
+    #[inline]
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&TentHash::finalize(self));
+    }
+}
+
+impl Reset for TentHash {
+    #[inline]
+    fn reset(&mut self) {
+        *self = TentHash::new();
+    }
+}
+
+impl FixedOutputReset for TentHash {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&TentHash::finalize(*self));
+        Reset::reset(self);
+    }
+}
@@ -0,0 +1,189 @@
+use core::{fmt, str::FromStr};
+
+use crate::{
+    encoding::{self, DecodeError},
+    DigestExt, DIGEST_SIZE,
+};
+
+/// A TentHash digest.
+///
+/// This is a thin wrapper around the `[u8; 20]` digest returned by
+/// [`hash()`](crate::hash) and [`TentHash::finalize()`](crate::TentHash::finalize),
+/// and converts to and from it freely.  Its main purpose is to provide
+/// conveniences like printing and parsing as hex, ordering, and use as a map
+/// key.
+///
+/// Note that equality comparison is *not* constant time.  This is fine because
+/// TentHash isn't intended for use where security matters anyway.
+///
+/// # Example
+///
+/// ```rust
+/// use tenthash::Digest;
+///
+/// let digest = Digest::from(tenthash::hash("Hello world!"));
+/// let text = digest.to_string();
+///
+/// assert_eq!(&text[..8], "155f0a35");
+/// assert_eq!(text.parse::<Digest>(), Ok(digest));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Digest([u8; DIGEST_SIZE]);
+
+impl Digest {
+    /// Creates a digest from its bytes.
+    #[inline(always)]
+    pub const fn from_bytes(bytes: [u8; DIGEST_SIZE]) -> Digest {
+        Digest(bytes)
+    }
+
+    /// Returns a reference to the bytes of the digest.
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8; DIGEST_SIZE] {
+        &self.0
+    }
+
+    /// Returns the bytes of the digest.
+    #[inline(always)]
+    pub const fn to_bytes(self) -> [u8; DIGEST_SIZE] {
+        self.0
+    }
+
+    /// Truncates the digest to 128 bits, returned as an array of 16 bytes.
+    #[inline(always)]
+    pub fn to_16_bytes(self) -> [u8; 16] {
+        self.0.to_16_bytes()
+    }
+
+    /// Truncates the digest to 128 bits, returned as a [`u128`].
+    ///
+    /// The digest bytes are interpreted as little endian.
+    #[inline(always)]
+    pub fn to_u128(self) -> u128 {
+        self.0.to_u128()
+    }
+}
+
+impl DigestExt for Digest {
+    #[inline(always)]
+    fn to_16_bytes(self) -> [u8; 16] {
+        Digest::to_16_bytes(self)
+    }
+
+    #[inline(always)]
+    fn to_u128(self) -> u128 {
+        Digest::to_u128(self)
+    }
+}
+
+impl From<[u8; DIGEST_SIZE]> for Digest {
+    #[inline(always)]
+    fn from(bytes: [u8; DIGEST_SIZE]) -> Digest {
+        Digest(bytes)
+    }
+}
+
+impl From<Digest> for [u8; DIGEST_SIZE] {
+    #[inline(always)]
+    fn from(digest: Digest) -> [u8; DIGEST_SIZE] {
+        digest.0
+    }
+}
+
+impl AsRef<[u8]> for Digest {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq<[u8; DIGEST_SIZE]> for Digest {
+    #[inline(always)]
+    fn eq(&self, other: &[u8; DIGEST_SIZE]) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<Digest> for [u8; DIGEST_SIZE] {
+    #[inline(always)]
+    fn eq(&self, other: &Digest) -> bool {
+        self == &other.0
+    }
+}
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Digest(")?;
+        fmt::LowerHex::fmt(self, f)?;
+        f.write_str(")")
+    }
+}
+
+/// Formats the digest as lowercase hex.
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(encoding::encode_hex(&self.0, &mut [0; encoding::HEX_LEN]))
+    }
+}
+
+impl fmt::UpperHex for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(encoding::encode_hex_upper(
+            &self.0,
+            &mut [0; encoding::HEX_LEN],
+        ))
+    }
+}
+
+/// Parses a digest from 40 hex digits.
+///
+/// Both lowercase and uppercase hex digits are accepted.
+impl FromStr for Digest {
+    type Err = ParseDigestError;
+
+    fn from_str(text: &str) -> Result<Digest, ParseDigestError> {
+        match encoding::decode_hex(text) {
+            Ok(bytes) => Ok(Digest(bytes)),
+            Err(DecodeError::InvalidLength(len)) => Err(ParseDigestError::InvalidLength(len)),
+            Err(DecodeError::InvalidCharacter(index)) => Err(ParseDigestError::InvalidDigit(index)),
+            // Hex has no trailing bits.
+            Err(DecodeError::NonZeroTrailingBits) => unreachable!(),
+        }
+    }
+}
+
+/// The error returned when parsing a [`Digest`] from a string fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseDigestError {
+    /// The string wasn't 40 bytes long.  Contains the actual length.
+    InvalidLength(usize),
+
+    /// The string contained a byte that isn't a hex digit.  Contains the
+    /// byte index of the offending byte.
+    InvalidDigit(usize),
+}
+
+impl fmt::Display for ParseDigestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ParseDigestError::InvalidLength(len) => write!(
+                f,
+                "invalid digest length: expected {} hex digits, got {}",
+                DIGEST_SIZE * 2,
+                len
+            ),
+            ParseDigestError::InvalidDigit(index) => {
+                write!(f, "invalid hex digit in digest at index {}", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDigestError {}
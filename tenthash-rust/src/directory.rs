@@ -0,0 +1,361 @@
+//! Fingerprinting of whole directory trees, available with the `std` feature.
+//!
+//! [`fingerprint()`] walks a directory tree, hashes every file in it with
+//! TentHash, and combines the results into a single root digest.  It also
+//! returns a manifest listing the digest of every entry in the tree.
+//!
+//! The root digest depends only on the names, types, contents, and (optionally)
+//! permissions of the entries in the tree.  In particular, it does *not*
+//! depend on the order that the file system lists directory entries in, on
+//! timestamps or ownership, or on the platform that computed it.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tenthash::directory::{fingerprint, Options};
+//!
+//! let fingerprint = fingerprint("some/directory", &Options::default()).unwrap();
+//!
+//! println!("{}", fingerprint.root);
+//! for entry in fingerprint.entries.iter() {
+//!     println!("{}  {}", entry.digest, entry.path);
+//! }
+//! ```
+//!
+//! # Canonical encoding
+//!
+//! Every entry in the tree has a *node digest*, computed as follows:
+//!
+//! - **File:** the TentHash of the file's contents.  This is the same as what
+//!   [`hash_file()`](crate::hash_file) returns.
+//! - **Symlink** (only with [`SymlinkPolicy::Record`]): the TentHash of the
+//!   symlink's target path, as UTF-8, with the platform's path separators
+//!   (`\` on Windows) replaced by `/`.  Other than that, the target path is
+//!   recorded verbatim, without any normalization.
+//! - **Directory:** the TentHash of the following byte sequence:
+//!   1. The 15 ascii bytes `tenthash-dir-v1`.
+//!   2. A flags byte.  Bit 0 is set if permissions are included, and all other
+//!      bits are zero.
+//!   3. A record for each child entry, in ascending byte-wise order of the
+//!      entries' UTF-8 names:
+//!      1. The entry type as one ascii byte: `f` for files, `d` for
+//!         directories, and `l` for symlinks.
+//!      2. The byte length of the entry's name, as a little-endian `u64`.
+//!      3. The entry's name, as UTF-8.
+//!      4. If permissions are included, the entry's permissions as a
+//!         little-endian `u32`.  See [`Options::include_permissions`] for how
+//!         these are determined.
+//!      5. The entry's 20-byte node digest.
+//!
+//! The root digest is the node digest of the root path itself.  Note that this
+//! means that fingerprinting a single file gives the same digest as hashing it
+//! directly.
+//!
+//! Entry names that aren't valid Unicode are rejected with an error, since
+//! they can't be encoded in a platform-independent way.
+
+use std::{
+    fs::{self, Metadata},
+    io,
+    path::{Path, PathBuf},
+    string::String,
+    vec::Vec,
+};
+
+use crate::{hash_file, Digest, TentHash};
+
+/// Magic bytes that start the encoding of every directory node.
+const DIRECTORY_MAGIC: &[u8; 15] = b"tenthash-dir-v1";
+
+/// How symlinks are handled when walking a directory tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum SymlinkPolicy {
+    /// Don't follow symlinks, and instead record them as symlink entries whose
+    /// node digest is the hash of their target path.
+    #[default]
+    Record,
+
+    /// Follow symlinks, treating them as whatever they point to.
+    ///
+    /// Symlinks that point to one of their own ancestor directories result in
+    /// an error, as do dangling symlinks.
+    Follow,
+
+    /// Skip symlinks entirely, as if they don't exist.
+    Skip,
+
+    /// Return an error when encountering a symlink.
+    Error,
+}
+
+/// Options controlling how a directory tree is fingerprinted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Options {
+    /// How to handle symlinks.
+    pub symlinks: SymlinkPolicy,
+
+    /// Whether to include entry permissions in the fingerprint.
+    ///
+    /// On Unix platforms, this is the permission bits of the entry's mode
+    /// (`mode & 0o7777`).  Other platforms only track whether an entry is
+    /// read-only, so permissions are derived from that: `0o555` (directories)
+    /// and `0o444` (other entries) when read-only, `0o755` and `0o644`
+    /// otherwise.
+    ///
+    /// Symlink permissions are always recorded as zero, since they're
+    /// meaningless on most platforms.
+    pub include_permissions: bool,
+}
+
+/// The type of an entry in a directory tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    File,
+    Directory,
+    Symlink,
+}
+
+impl EntryKind {
+    /// The byte used to identify the entry type in the canonical encoding.
+    fn code(self) -> u8 {
+        match self {
+            EntryKind::File => b'f',
+            EntryKind::Directory => b'd',
+            EntryKind::Symlink => b'l',
+        }
+    }
+}
+
+/// An entry in a directory tree's manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The path of the entry relative to the root, with components separated
+    /// by `/` regardless of platform.
+    pub path: String,
+
+    /// The type of the entry.
+    pub kind: EntryKind,
+
+    /// The entry's permissions, if they were included.
+    pub permissions: Option<u32>,
+
+    /// The entry's node digest.
+    pub digest: Digest,
+}
+
+/// The result of fingerprinting a directory tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    /// The digest of the whole tree.
+    pub root: Digest,
+
+    /// Every entry in the tree, excluding the root itself.
+    ///
+    /// Entries are listed depth-first, with each directory's entries in
+    /// canonical order and each directory listed before its contents.
+    pub entries: Vec<Entry>,
+}
+
+/// Fingerprints the directory tree at `path`.
+///
+/// `path` is typically a directory, but can also be a file or (unless the
+/// symlink policy is [`SymlinkPolicy::Error`] or [`SymlinkPolicy::Skip`]) a
+/// symlink.
+///
+/// Special files such as sockets, fifos, and devices result in an error.
+pub fn fingerprint(path: impl AsRef<Path>, options: &Options) -> io::Result<Fingerprint> {
+    let path = path.as_ref();
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.file_type().is_symlink() && options.symlinks == SymlinkPolicy::Skip {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the root path is a symlink, and the symlink policy is to skip symlinks",
+        ));
+    }
+
+    let mut walker = Walker {
+        options,
+        entries: Vec::new(),
+        ancestors: Vec::new(),
+    };
+    let (_, _, root) = walker.node(path, metadata, "")?;
+
+    Ok(Fingerprint {
+        root,
+        entries: walker.entries,
+    })
+}
+
+struct Walker<'a> {
+    options: &'a Options,
+    entries: Vec<Entry>,
+
+    // Canonicalized paths of the directories currently being walked, for
+    // detecting symlink cycles.  Only used when following symlinks.
+    ancestors: Vec<PathBuf>,
+}
+
+impl Walker<'_> {
+    /// Computes the node digest of the entry at `path`, adding all of its
+    /// descendants to the manifest.
+    ///
+    /// `metadata` is the entry's metadata *without* following symlinks.
+    fn node(
+        &mut self,
+        path: &Path,
+        metadata: Metadata,
+        rel_path: &str,
+    ) -> io::Result<(EntryKind, Metadata, Digest)> {
+        let metadata = if metadata.file_type().is_symlink() {
+            match self.options.symlinks {
+                SymlinkPolicy::Record => {
+                    let target = symlink_target(&fs::read_link(path)?)?;
+                    let digest = Digest::from(crate::hash(target));
+                    return Ok((EntryKind::Symlink, metadata, digest));
+                }
+                SymlinkPolicy::Follow => fs::metadata(path)?,
+                SymlinkPolicy::Skip => unreachable!(),
+                SymlinkPolicy::Error => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        std::format!("encountered symlink: {}", path.display()),
+                    ));
+                }
+            }
+        } else {
+            metadata
+        };
+
+        if metadata.is_file() {
+            Ok((EntryKind::File, metadata, hash_file(path)?.into()))
+        } else if metadata.is_dir() {
+            let digest = self.directory(path, rel_path)?;
+            Ok((EntryKind::Directory, metadata, digest))
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                std::format!("unsupported file type: {}", path.display()),
+            ))
+        }
+    }
+
+    /// Computes the node digest of the directory at `path`.
+    fn directory(&mut self, path: &Path, rel_path: &str) -> io::Result<Digest> {
+        let following = self.options.symlinks == SymlinkPolicy::Follow;
+        if following {
+            let canonical = fs::canonicalize(path)?;
+            if self.ancestors.contains(&canonical) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    std::format!("symlink cycle detected at: {}", path.display()),
+                ));
+            }
+            self.ancestors.push(canonical);
+        }
+
+        // Collect and sort the children.
+        let mut children = Vec::new();
+        for dir_entry in fs::read_dir(path)? {
+            let dir_entry = dir_entry?;
+            let metadata = fs::symlink_metadata(dir_entry.path())?;
+            if metadata.file_type().is_symlink() && self.options.symlinks == SymlinkPolicy::Skip {
+                continue;
+            }
+            let name = dir_entry.file_name().into_string().map_err(|name| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    std::format!("file name is not valid Unicode: {:?}", name),
+                )
+            })?;
+            children.push((name, dir_entry.path(), metadata));
+        }
+        children.sort_unstable_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+        // Hash the children and build the directory node.
+        let flags = self.options.include_permissions as u8;
+        let mut hasher = TentHash::new();
+        hasher.update(DIRECTORY_MAGIC);
+        hasher.update([flags]);
+
+        for (name, child_path, metadata) in children {
+            let child_rel_path = if rel_path.is_empty() {
+                name.clone()
+            } else {
+                std::format!("{}/{}", rel_path, name)
+            };
+
+            // Add the entry to the manifest before its descendants, and fill
+            // in the details afterwards.
+            let index = self.entries.len();
+            self.entries.push(Entry {
+                path: child_rel_path.clone(),
+                kind: EntryKind::File,
+                permissions: None,
+                digest: Digest::from_bytes([0; 20]),
+            });
+
+            let (kind, metadata, digest) = self.node(&child_path, metadata, &child_rel_path)?;
+            let permissions = if self.options.include_permissions {
+                Some(permissions(kind, &metadata))
+            } else {
+                None
+            };
+
+            hasher.update([kind.code()]);
+            hasher.update((name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            if let Some(permissions) = permissions {
+                hasher.update(permissions.to_le_bytes());
+            }
+            hasher.update(digest.as_bytes());
+
+            let entry = &mut self.entries[index];
+            entry.kind = kind;
+            entry.permissions = permissions;
+            entry.digest = digest;
+        }
+
+        if following {
+            self.ancestors.pop();
+        }
+
+        Ok(hasher.finalize().into())
+    }
+}
+
+#[cfg(unix)]
+fn permissions(kind: EntryKind, metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    match kind {
+        EntryKind::Symlink => 0,
+        _ => metadata.permissions().mode() & 0o7777,
+    }
+}
+
+#[cfg(not(unix))]
+fn permissions(kind: EntryKind, metadata: &Metadata) -> u32 {
+    let readonly = metadata.permissions().readonly();
+    match (kind, readonly) {
+        (EntryKind::Symlink, _) => 0,
+        (EntryKind::Directory, true) => 0o555,
+        (EntryKind::Directory, false) => 0o755,
+        (EntryKind::File, true) => 0o444,
+        (EntryKind::File, false) => 0o644,
+    }
+}
+
+fn path_to_str(path: &Path) -> io::Result<&str> {
+    path.to_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            std::format!("path is not valid Unicode: {}", path.display()),
+        )
+    })
+}
+
+/// Returns a symlink's target path as it's hashed: as UTF-8, and with `/` as
+/// the path separator on all platforms.
+fn symlink_target(target: &Path) -> io::Result<String> {
+    Ok(path_to_str(target)?.replace(std::path::MAIN_SEPARATOR, "/"))
+}
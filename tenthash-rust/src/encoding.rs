@@ -0,0 +1,253 @@
+//! Text encodings for digests: hex, base32, and base64.
+//!
+//! Everything here is allocation free and works in `no_std` environments.
+//! Each encoding has three functions:
+//!
+//! - `encode_*()` encodes a digest into a fixed-size byte array, and returns
+//!   the encoded text as a `&str` borrowed from it.
+//! - `write_*()` writes the encoded digest to a [`core::fmt::Write`], such as
+//!   a `String` or a `Formatter`.
+//! - `decode_*()` parses a digest back from text.
+//!
+//! The supported encodings are:
+//!
+//! | Encoding       | Length | Alphabet                                  |
+//! |----------------|--------|-------------------------------------------|
+//! | Hex            | 40     | `0-9 a-f` (or `A-F` for uppercase)        |
+//! | Base32         | 32     | `A-Z 2-7`, per RFC 4648 section 6         |
+//! | Base64 URL     | 27     | `A-Z a-z 0-9 - _`, per RFC 4648 section 5 |
+//!
+//! None of the encodings use padding.  Base32 is case insensitive and
+//! doesn't need padding for 160-bit digests anyway, which makes it a good
+//! choice for file names.  Base64 is the most compact, and uses the URL and
+//! file name safe alphabet.
+//!
+//! Decoding is strict: the text must have exactly the right length, contain
+//! only characters from the encoding's alphabet, and (for base64) have zero
+//! bits in the unused low bits of the last character, so that every digest
+//! has exactly one valid encoding (modulo case, where case insensitive).
+//!
+//! # Example
+//!
+//! ```rust
+//! use tenthash::encoding;
+//!
+//! let digest = tenthash::hash("Hello world!");
+//!
+//! let mut buf = [0u8; encoding::BASE32_LEN];
+//! let text = encoding::encode_base32(&digest, &mut buf);
+//!
+//! assert_eq!(encoding::decode_base32(text), Ok(digest));
+//! ```
+
+use core::fmt;
+
+use crate::DIGEST_SIZE;
+
+/// The length of a hex-encoded digest.
+pub const HEX_LEN: usize = DIGEST_SIZE * 2;
+
+/// The length of a base32-encoded digest.
+pub const BASE32_LEN: usize = (DIGEST_SIZE * 8).div_ceil(5);
+
+/// The length of a base64-encoded digest.
+pub const BASE64_LEN: usize = (DIGEST_SIZE * 8).div_ceil(6);
+
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+//-------------------------------------------------------------
+// Encoding.
+
+/// Encodes a digest as lowercase hex.
+pub fn encode_hex<'a>(digest: &[u8; DIGEST_SIZE], out: &'a mut [u8; HEX_LEN]) -> &'a str {
+    encode_bits(digest, 4, HEX_LOWER, out)
+}
+
+/// Encodes a digest as uppercase hex.
+pub fn encode_hex_upper<'a>(digest: &[u8; DIGEST_SIZE], out: &'a mut [u8; HEX_LEN]) -> &'a str {
+    encode_bits(digest, 4, HEX_UPPER, out)
+}
+
+/// Encodes a digest as RFC 4648 base32, without padding.
+pub fn encode_base32<'a>(digest: &[u8; DIGEST_SIZE], out: &'a mut [u8; BASE32_LEN]) -> &'a str {
+    encode_bits(digest, 5, BASE32, out)
+}
+
+/// Encodes a digest as RFC 4648 URL-safe base64, without padding.
+pub fn encode_base64url<'a>(digest: &[u8; DIGEST_SIZE], out: &'a mut [u8; BASE64_LEN]) -> &'a str {
+    encode_bits(digest, 6, BASE64_URL, out)
+}
+
+/// Writes a digest as lowercase hex.
+pub fn write_hex<W: fmt::Write + ?Sized>(digest: &[u8; DIGEST_SIZE], w: &mut W) -> fmt::Result {
+    w.write_str(encode_hex(digest, &mut [0; HEX_LEN]))
+}
+
+/// Writes a digest as uppercase hex.
+pub fn write_hex_upper<W: fmt::Write + ?Sized>(
+    digest: &[u8; DIGEST_SIZE],
+    w: &mut W,
+) -> fmt::Result {
+    w.write_str(encode_hex_upper(digest, &mut [0; HEX_LEN]))
+}
+
+/// Writes a digest as RFC 4648 base32, without padding.
+pub fn write_base32<W: fmt::Write + ?Sized>(digest: &[u8; DIGEST_SIZE], w: &mut W) -> fmt::Result {
+    w.write_str(encode_base32(digest, &mut [0; BASE32_LEN]))
+}
+
+/// Writes a digest as RFC 4648 URL-safe base64, without padding.
+pub fn write_base64url<W: fmt::Write + ?Sized>(
+    digest: &[u8; DIGEST_SIZE],
+    w: &mut W,
+) -> fmt::Result {
+    w.write_str(encode_base64url(digest, &mut [0; BASE64_LEN]))
+}
+
+/// Like `encode_hex()`, but for any number of bytes.
+///
+/// `out` must be at least twice as long as `bytes`.
+pub(crate) fn encode_hex_bytes<'a>(bytes: &[u8], out: &'a mut [u8]) -> &'a str {
+    encode_bits(bytes, 4, HEX_LOWER, &mut out[..(bytes.len() * 2)])
+}
+
+/// Encodes `digest` into `out` with `bits` bits per character, most
+/// significant bits first.  The last character is padded with zero bits.
+fn encode_bits<'a>(digest: &[u8], bits: u32, alphabet: &[u8], out: &'a mut [u8]) -> &'a str {
+    let mask = (1u32 << bits) - 1;
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    let mut chars = out.iter_mut();
+
+    for &byte in digest {
+        acc = (acc << 8) | byte as u32;
+        acc_bits += 8;
+        while acc_bits >= bits {
+            acc_bits -= bits;
+            *chars.next().unwrap() = alphabet[((acc >> acc_bits) & mask) as usize];
+        }
+        acc &= (1 << acc_bits) - 1;
+    }
+    if acc_bits > 0 {
+        *chars.next().unwrap() = alphabet[((acc << (bits - acc_bits)) & mask) as usize];
+    }
+    debug_assert!(chars.next().is_none());
+
+    // The alphabets are all ascii, so this can't fail.
+    core::str::from_utf8(out).unwrap()
+}
+
+//-------------------------------------------------------------
+// Decoding.
+
+/// Decodes a digest from hex.
+///
+/// Both lowercase and uppercase digits are accepted.
+pub fn decode_hex(text: impl AsRef<[u8]>) -> Result<[u8; DIGEST_SIZE], DecodeError> {
+    decode_hex_bytes(text.as_ref())
+}
+
+/// Like `decode_hex()`, but for any number of bytes.
+pub(crate) fn decode_hex_bytes<const N: usize>(text: &[u8]) -> Result<[u8; N], DecodeError> {
+    decode_bits(text, 4, |c| match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    })
+}
+
+/// Decodes a digest from RFC 4648 base32, without padding.
+///
+/// Both lowercase and uppercase letters are accepted.
+pub fn decode_base32(text: impl AsRef<[u8]>) -> Result<[u8; DIGEST_SIZE], DecodeError> {
+    decode_bits(text.as_ref(), 5, |c| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a'),
+        b'2'..=b'7' => Some(c - b'2' + 26),
+        _ => None,
+    })
+}
+
+/// Decodes a digest from RFC 4648 URL-safe base64, without padding.
+pub fn decode_base64url(text: impl AsRef<[u8]>) -> Result<[u8; DIGEST_SIZE], DecodeError> {
+    decode_bits(text.as_ref(), 6, |c| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    })
+}
+
+/// The inverse of `encode_bits()`, with `char_value` mapping characters to
+/// their values.
+fn decode_bits<const N: usize>(
+    text: &[u8],
+    bits: u32,
+    char_value: impl Fn(u8) -> Option<u8>,
+) -> Result<[u8; N], DecodeError> {
+    let expected_len = (N * 8).div_ceil(bits as usize);
+    if text.len() != expected_len {
+        return Err(DecodeError::InvalidLength(text.len()));
+    }
+
+    let mut digest = [0u8; N];
+    let mut bytes = digest.iter_mut();
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+
+    for (i, &c) in text.iter().enumerate() {
+        let value = char_value(c).ok_or(DecodeError::InvalidCharacter(i))?;
+        acc = (acc << bits) | value as u32;
+        acc_bits += bits;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            *bytes.next().unwrap() = (acc >> acc_bits) as u8;
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    if acc != 0 {
+        return Err(DecodeError::NonZeroTrailingBits);
+    }
+
+    Ok(digest)
+}
+
+/// The error returned when decoding a digest from text fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The text has the wrong length for the encoding.  Contains the actual
+    /// length.
+    InvalidLength(usize),
+
+    /// The text contains a byte that isn't in the encoding's alphabet.
+    /// Contains the byte index of the offending byte.
+    InvalidCharacter(usize),
+
+    /// The unused low bits of the last character aren't zero.
+    NonZeroTrailingBits,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidLength(len) => {
+                write!(f, "invalid encoded digest length: {}", len)
+            }
+            DecodeError::InvalidCharacter(index) => {
+                write!(f, "invalid character in encoded digest at index {}", index)
+            }
+            DecodeError::NonZeroTrailingBits => {
+                f.write_str("encoded digest has non-zero trailing bits")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
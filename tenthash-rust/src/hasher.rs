@@ -0,0 +1,216 @@
+//! Adapters for `core::hash`, so that TentHash can be used with `Hash` types
+//! and hash maps.
+
+use core::hash::{BuildHasher, Hasher};
+
+use crate::{TentHash, DIGEST_SIZE};
+
+/// An adapter that implements [`Hasher`] with TentHash.
+///
+/// This lets TentHash be used with anything that implements [`Hash`], such
+/// as `#[derive(Hash)]` types, and as the hasher of `HashMap`s and
+/// `HashSet`s (see [`BuildTentHasher`]).  [`finish()`](Hasher::finish)
+/// returns the first 8 bytes of the digest as a little-endian `u64`, and
+/// [`finish_digest()`](HasherExt::finish_digest) returns the full 160-bit
+/// digest.
+///
+/// # Portability
+///
+/// The bytes that a [`Hash`] implementation feeds to the hasher are *not*
+/// specified, and can differ between Rust versions, crate versions, and
+/// platforms.  So unlike the digests of [`hash()`](crate::hash) and
+/// [`TentHash`], digests computed via `Hash` implementations should not be
+/// stored or compared across processes unless they're all built the same
+/// way.
+///
+/// `TentHasher` does what it can to mitigate this: integers passed to the
+/// `write_*()` methods are always hashed as little-endian bytes, and `usize`
+/// and `isize` are always hashed as 64 bits.  This makes hashes of integers
+/// and of simple structs of integers consistent across platforms.  But the
+/// encoding of other types, such as slices and strings, is still up to their
+/// `Hash` implementations.
+///
+/// When truly stable hashes are needed, the robust approach is to define a
+/// byte encoding for your data and hash it directly with
+/// [`TentHash::update()`] or [`hash()`](crate::hash).  You can also call
+/// [`write()`](Hasher::write) on a `TentHasher` directly, which is exactly
+/// equivalent to [`TentHash::update()`].
+///
+/// # Example
+///
+/// ```rust
+/// use core::hash::Hash;
+/// use tenthash::{HasherExt, TentHasher};
+///
+/// #[derive(Hash)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let mut hasher = TentHasher::new();
+/// Point { x: 1, y: 2 }.hash(&mut hasher);
+/// let digest = hasher.finish_digest();
+///
+/// // Integers are hashed as little-endian bytes.
+/// let mut bytes = Vec::new();
+/// bytes.extend_from_slice(&1i32.to_le_bytes());
+/// bytes.extend_from_slice(&2i32.to_le_bytes());
+/// assert_eq!(digest, tenthash::hash(bytes));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct TentHasher {
+    hash: TentHash,
+}
+
+impl TentHasher {
+    pub fn new() -> TentHasher {
+        TentHasher {
+            hash: TentHash::new(),
+        }
+    }
+
+    /// Creates a hasher for a seeded variant of TentHash.
+    ///
+    /// See [`TentHash::with_seed()`].
+    pub fn with_seed(seed: u64) -> TentHasher {
+        TentHasher {
+            hash: TentHash::with_seed(seed),
+        }
+    }
+}
+
+impl Default for TentHasher {
+    fn default() -> TentHasher {
+        TentHasher::new()
+    }
+}
+
+/// A helper trait for getting the full digest from a TentHash [`Hasher`].
+pub trait HasherExt: Hasher {
+    /// Returns the full 160-bit digest of the data written so far.
+    ///
+    /// Like [`finish()`](Hasher::finish), this doesn't reset the hasher, and
+    /// more data can still be written afterwards.
+    fn finish_digest(&self) -> [u8; DIGEST_SIZE];
+}
+
+impl HasherExt for TentHasher {
+    #[inline]
+    fn finish_digest(&self) -> [u8; DIGEST_SIZE] {
+        self.hash.finalize()
+    }
+}
+
+impl Hasher for TentHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.finish_digest();
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+
+    // Integers are always written as little endian, so that their hashes
+    // don't depend on the platform.
+
+    #[inline]
+    fn write_u16(&mut self, n: u16) {
+        self.write(&n.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, n: u32) {
+        self.write(&n.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, n: u128) {
+        self.write(&n.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, n: i16) {
+        self.write_u16(n as u16);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, n: i32) {
+        self.write_u32(n as u32);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, n: i64) {
+        self.write_u64(n as u64);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, n: i128) {
+        self.write_u128(n as u128);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, n: isize) {
+        self.write_i64(n as i64);
+    }
+}
+
+/// A [`BuildHasher`] that creates [`TentHasher`]s.
+///
+/// This is what to use to make `HashMap`s and `HashSet`s use TentHash.  Note
+/// that TentHash isn't designed for hash tables, and is considerably slower
+/// than hashers that are, especially for small keys.  It also isn't resistant
+/// to HashDoS attacks, so don't use it for maps with untrusted keys.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use tenthash::BuildTentHasher;
+///
+/// let mut map: HashMap<&str, i32, BuildTentHasher> = HashMap::default();
+/// map.insert("one", 1);
+///
+/// assert_eq!(map.get("one"), Some(&1));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct BuildTentHasher {
+    seed: Option<u64>,
+}
+
+impl BuildTentHasher {
+    pub fn new() -> BuildTentHasher {
+        BuildTentHasher { seed: None }
+    }
+
+    /// Creates a `BuildTentHasher` whose hashers use a seeded variant of
+    /// TentHash.
+    ///
+    /// See [`TentHash::with_seed()`].
+    pub fn with_seed(seed: u64) -> BuildTentHasher {
+        BuildTentHasher { seed: Some(seed) }
+    }
+}
+
+impl BuildHasher for BuildTentHasher {
+    type Hasher = TentHasher;
+
+    fn build_hasher(&self) -> TentHasher {
+        match self.seed {
+            Some(seed) => TentHasher::with_seed(seed),
+            None => TentHasher::new(),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! `std::io` integration, available with the `std` feature.
+
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use crate::{TentHash, DIGEST_SIZE};
+
+/// Size of the buffer used when reading data for hashing, in bytes.
+///
+/// This is a multiple of the block size, so that full reads are hashed
+/// directly without going through `TentHash`'s internal buffer.
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+impl TentHash {
+    /// Appends all data from a reader to the data stream being hashed.
+    ///
+    /// Reads until EOF, and returns the number of bytes read.  Reads that fail
+    /// with [`io::ErrorKind::Interrupted`] are retried, and any other error is
+    /// returned immediately.  In the error case, the data read up to that
+    /// point has already been appended to the hash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tenthash::TentHash;
+    /// let mut hasher = TentHash::new();
+    /// let byte_count = hasher.update_reader(&b"Hello world!"[..]).unwrap();
+    ///
+    /// assert_eq!(byte_count, 12);
+    /// assert_eq!(hasher.finalize(), tenthash::hash("Hello world!"));
+    /// ```
+    pub fn update_reader(&mut self, mut reader: impl Read) -> io::Result<u64> {
+        let mut buf = std::vec![0u8; READ_BUFFER_SIZE];
+        let mut byte_count = 0u64;
+
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Ok(byte_count),
+                Ok(n) => {
+                    self.update(&buf[..n]);
+                    byte_count += n as u64;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Appends written data to the data stream being hashed.
+///
+/// Writes never fail and always consume the entire buffer, and `flush()` is a
+/// no-op.  This makes it possible to e.g. [`io::copy()`] data directly into the
+/// hasher.
+impl Write for TentHash {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.update(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Computes TentHash for the contents of a file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// let hash = tenthash::hash_file("some/file.txt").unwrap();
+/// ```
+pub fn hash_file(path: impl AsRef<Path>) -> io::Result<[u8; DIGEST_SIZE]> {
+    let mut hasher = TentHash::new();
+    hasher.update_reader(File::open(path)?)?;
+    Ok(hasher.finalize())
+}
@@ -19,13 +19,132 @@
 //!
 //! assert_eq!(&hash[..4], &[0x15, 0x5f, 0xa, 0x35]);
 //! ```
+//!
+//!
+//! # Crate features
+//!
+//! This crate is `no_std` and allocation-free by default.  The following
+//! optional features are available:
+//!
+//! - `std`: implements `std::io::Write` for [`TentHash`], and adds
+//!   `TentHash::update_reader()` and `hash_file()` for hashing data from
+//!   readers and files, `HashingReader` and `HashingWriter` for hashing data
+//!   as it passes through, and `hash_many()` for hashing many independent
+//!   messages at once.  Also enables SIMD acceleration of `hash_many()` and
+//!   friends via runtime CPU feature detection, and adds the `directory`
+//!   module for fingerprinting whole directory trees.
+//! - `avx512`: also uses AVX-512 for `hash_many()` and friends on CPUs that
+//!   support it.  Needs Rust 1.89 or later.  Implies `std`.
+//! - `digest`: implements the [RustCrypto `digest`](https://docs.rs/digest)
+//!   traits for [`TentHash`], so that it can be used with code that's generic
+//!   over hash functions.
+//! - `serde`: implements `Serialize` and `Deserialize` for [`TentHash`]
+//!   (using the format of [`TentHash::export_state()`]), [`Digest`], and
+//!   `multihash::Multihash`.  Hasher states and digests are serialized as hex
+//!   strings in human-readable formats and as bytes in binary formats.  Also
+//!   adds the `serde_digest` module, for doing the same with plain `[u8; 20]`
+//!   digests.
+//! - `mmap`: adds `hash_file_mmap()`, for hashing files via memory mapping.
+//!   It's `unsafe`, since the file must not be truncated while it's being
+//!   hashed.  Implies `std`.
+//! - `tokio`: implements tokio's `AsyncRead` and `AsyncWrite` for
+//!   `HashingReader` and `HashingWriter`.  Implies `std`.
+//! - `futures-io`: implements futures-io's `AsyncRead` and `AsyncWrite` for
+//!   `HashingReader` and `HashingWriter`.  Implies `std`.
+//! - `rayon`: adds `tree::hash_parallel()`, for computing the TentHash Tree
+//!   mode on multiple threads.
 
 #![no_std]
-#![forbid(unsafe_code)]
+// The only `unsafe` code is for runtime CPU feature detection and memory
+// mapping, both of which need `std`, and is confined to `multi::x86_64` and
+// `mmap` with `#[allow(unsafe_code)]`.  `forbid` can't be overridden like
+// that, so with `std` the rest of the crate gets `deny` instead.
+#![cfg_attr(not(feature = "std"), forbid(unsafe_code))]
+#![cfg_attr(feature = "std", deny(unsafe_code))]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod const_hash;
+mod digest_type;
+mod hasher;
+mod multi;
+mod state;
+mod verify;
+
+pub mod chunking;
+pub mod encoding;
+pub mod merkle;
+pub mod multihash;
+pub mod tree;
+
+#[cfg(feature = "std")]
+mod adapters;
+
+#[cfg(feature = "std")]
+mod io;
+
+#[cfg(feature = "mmap")]
+mod mmap;
+
+#[cfg(feature = "std")]
+pub mod directory;
+
+#[cfg(feature = "digest")]
+mod digest_traits;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(feature = "serde")]
+pub mod serde_digest;
+
+pub use const_hash::const_hash;
+pub use digest_type::{Digest, ParseDigestError};
+pub use hasher::{BuildTentHasher, HasherExt, TentHasher};
+pub use multi::{hash_4x, hash_8x, hash_many_into};
+pub use state::StateError;
+pub use verify::{verify, ExpectedDigest, Mismatch};
+
+#[cfg(feature = "std")]
+pub use adapters::{HashingReader, HashingWriter};
+
+#[cfg(feature = "std")]
+pub use io::hash_file;
+
+#[cfg(feature = "mmap")]
+pub use mmap::hash_file_mmap;
+
+#[cfg(feature = "std")]
+pub use multi::hash_many;
 
 const DIGEST_SIZE: usize = 160 / 8; // Digest size, in bytes.
+
+/// The size of a digest truncated to 128 bits, in bytes.
+///
+/// See [`DigestExt::to_16_bytes()`].
+pub const TRUNCATED_SIZE: usize = 128 / 8;
 const BLOCK_SIZE: usize = 256 / 8; // Internal block size of the hash, in bytes.
 
+/// The initial hash state.
+const INITIAL_STATE: [u64; 4] = [
+    0x5d6daffc4411a967,
+    0xe22d4dea68577f34,
+    0xca50864d814cbc2e,
+    0x894e29b9611eb173,
+];
+
+/// The rotation constants used in `mix_state()`.
+const ROTATIONS: &[[u32; 2]] = &[
+    [16, 28],
+    [14, 57],
+    [11, 22],
+    [35, 34],
+    [57, 16],
+    [59, 40],
+    [44, 13],
+];
+
 /// Computes TentHash in one go for a contiguous slice of data.
 ///
 /// # Example
@@ -36,12 +155,7 @@ const BLOCK_SIZE: usize = 256 / 8; // Internal block size of the hash, in bytes.
 /// assert_eq!(&hash[..4], &[0x15, 0x5f, 0xa, 0x35]);
 /// ```
 pub fn hash(data: impl AsRef<[u8]>) -> [u8; DIGEST_SIZE] {
-    let mut state = [
-        0x5d6daffc4411a967,
-        0xe22d4dea68577f34,
-        0xca50864d814cbc2e,
-        0x894e29b9611eb173,
-    ];
+    let mut state = INITIAL_STATE;
 
     let mut data = data.as_ref();
     let message_bit_length = data.len() as u64 * 8;
@@ -56,7 +170,7 @@ pub fn hash(data: impl AsRef<[u8]>) -> [u8; DIGEST_SIZE] {
     // Process any remaining data if needed.
     if !data.is_empty() {
         let mut buffer = [0u8; BLOCK_SIZE];
-        (&mut buffer[..data.len()]).copy_from_slice(data);
+        buffer[..data.len()].copy_from_slice(data);
         xor_data_into_state(&mut state, &buffer);
         mix_state(&mut state);
     }
@@ -72,7 +186,7 @@ pub fn hash(data: impl AsRef<[u8]>) -> [u8; DIGEST_SIZE] {
     digest[0..8].copy_from_slice(&state[0].to_le_bytes());
     digest[8..16].copy_from_slice(&state[1].to_le_bytes());
     digest[16..20].copy_from_slice(&state[2].to_le_bytes()[0..4]);
-    return digest;
+    digest
 }
 
 /// Computes TentHash incrementally, taking input data in chunks.
@@ -113,18 +227,68 @@ pub struct TentHash {
 impl TentHash {
     pub fn new() -> TentHash {
         TentHash {
-            state: [
-                0x5d6daffc4411a967,
-                0xe22d4dea68577f34,
-                0xca50864d814cbc2e,
-                0x894e29b9611eb173,
-            ],
+            state: INITIAL_STATE,
             buf: [0; BLOCK_SIZE],
             buf_length: 0,
             message_length: 0,
         }
     }
 
+    /// Creates a hasher for a seeded variant of TentHash, with a 64-bit seed.
+    ///
+    /// This is the same as [`with_seed_bytes()`](TentHash::with_seed_bytes)
+    /// with the seed's eight little-endian bytes followed by 24 zero bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tenthash::TentHash;
+    /// let mut hasher = TentHash::with_seed(42);
+    /// hasher.update("Hello world!");
+    /// let digest = hasher.finalize();
+    ///
+    /// assert_ne!(digest, tenthash::hash("Hello world!"));
+    /// ```
+    pub fn with_seed(seed: u64) -> TentHash {
+        let mut bytes = [0u8; BLOCK_SIZE];
+        bytes[..8].copy_from_slice(&seed.to_le_bytes());
+        TentHash::with_seed_bytes(bytes)
+    }
+
+    /// Creates a hasher for a seeded variant of TentHash, with a 256-bit
+    /// seed.
+    ///
+    /// Each seed gives an independent hash function, which is useful e.g. for
+    /// sharding or for hash tables that need multiple hash functions.  Seeded
+    /// digests are unrelated to the unseeded digests of the same data, and
+    /// to the digests of the same data with any other seed.
+    ///
+    /// The seed is xored into the initial hash state, which is then mixed
+    /// once, the same way as a block of data.  Unlike data, though, the seed
+    /// doesn't count towards the message length, so hashing data with a seed
+    /// is not the same as hashing the seed followed by the data without one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tenthash::TentHash;
+    /// let seed = [7u8; 32];
+    ///
+    /// let mut hasher = TentHash::with_seed_bytes(seed);
+    /// hasher.update("Hello world!");
+    ///
+    /// assert_ne!(
+    ///     hasher.finalize(),
+    ///     tenthash::hash([&seed[..], b"Hello world!"].concat()),
+    /// );
+    /// ```
+    pub fn with_seed_bytes(seed: [u8; BLOCK_SIZE]) -> TentHash {
+        let mut hasher = TentHash::new();
+        xor_data_into_state(&mut hasher.state, &seed);
+        mix_state(&mut hasher.state);
+        hasher
+    }
+
     /// Appends data to the data stream being hashed.
     ///
     /// Call this repeatedly to incrementally append more and more data.
@@ -146,7 +310,7 @@ impl TentHash {
             } else {
                 // Fill the buffer.
                 let n = (BLOCK_SIZE - self.buf_length).min(data.len());
-                (&mut self.buf[self.buf_length..(self.buf_length + n)]).copy_from_slice(&data[..n]);
+                self.buf[self.buf_length..(self.buf_length + n)].copy_from_slice(&data[..n]);
                 data = &data[n..];
                 self.buf_length += n;
             }
@@ -157,7 +321,7 @@ impl TentHash {
     pub fn finalize(mut self) -> [u8; DIGEST_SIZE] {
         // Hash the remaining bytes if there are any.
         if self.buf_length > 0 {
-            (&mut self.buf[self.buf_length..]).fill(0); // Pad with zeros as needed.
+            self.buf[self.buf_length..].fill(0); // Pad with zeros as needed.
             xor_data_into_state(&mut self.state, &self.buf);
             mix_state(&mut self.state);
         }
@@ -173,7 +337,13 @@ impl TentHash {
         digest[0..8].copy_from_slice(&self.state[0].to_le_bytes());
         digest[8..16].copy_from_slice(&self.state[1].to_le_bytes());
         digest[16..20].copy_from_slice(&self.state[2].to_le_bytes()[0..4]);
-        return digest;
+        digest
+    }
+}
+
+impl Default for TentHash {
+    fn default() -> TentHash {
+        TentHash::new()
     }
 }
 
@@ -197,16 +367,6 @@ fn xor_data_into_state(state: &mut [u64; 4], data: &[u8]) {
 /// Running it twice achieves full 256-bit diffusion.
 #[inline(always)]
 fn mix_state(state: &mut [u64; 4]) {
-    const ROTATIONS: &[[u32; 2]] = &[
-        [16, 28],
-        [14, 57],
-        [11, 22],
-        [35, 34],
-        [57, 16],
-        [59, 40],
-        [44, 13],
-    ];
-
     for rot_pair in ROTATIONS.iter() {
         state[0] = state[0].wrapping_add(state[2]);
         state[2] = state[2].rotate_left(rot_pair[0]) ^ state[0];
@@ -0,0 +1,382 @@
+//! Merkle trees over lists of leaves, with compact inclusion proofs.
+//!
+//! A Merkle tree combines the digests of many leaves (e.g. the chunks of a
+//! file, or the entries of a manifest) into a single root digest.  An
+//! [inclusion proof](Proof) then shows that a given leaf is part of the tree
+//! with a given root, using only about `log2(n)` digests instead of the
+//! whole list of leaves.
+//!
+//! The tree structure and the proof format follow the Merkle trees of
+//! [RFC 9162](https://www.rfc-editor.org/rfc/rfc9162#section-2.1) (Certificate
+//! Transparency), with TentHash as the hash function:
+//!
+//! - The digest of a leaf is `TentHash(0x00 || data)`, see [`leaf_digest()`].
+//! - The digest of an inner node is `TentHash(0x01 || left || right)`, see
+//!   [`node_digest()`].
+//! - For `n > 1` leaves, the left subtree holds the first `k` leaves and the
+//!   right subtree the rest, where `k` is the largest power of two less than
+//!   `n`.  A single leaf is its own root.
+//! - The root of an empty tree is `TentHash()` of no data.
+//!
+//! The distinct leaf and node prefixes make sure that a leaf can never be
+//! passed off as an inner node or vice versa.
+//!
+//! Everything here is allocation free and works in `no_std` environments.
+//! Functions take the leaves as slices, and computing the root or a proof
+//! takes `O(n)` time.
+//!
+//! As with TentHash itself, this is meant for integrity checking under
+//! non-adversarial conditions, and must not be relied on for security.
+//!
+//! # `merkle` vs. TentHash Tree
+//!
+//! The [`tree`](crate::tree) module's TentHash Tree has the same tree shape,
+//! but it's a different construction with incompatible digests.  It hashes a
+//! single byte stream in fixed-size chunks, and uses domain separation
+//! suffixes rather than prefixes to keep the chunk data block aligned for
+//! speed.  This module instead works on caller-defined leaves of any size,
+//! and follows RFC 9162 so that individual leaves can be proven to be part
+//! of the tree.  Use this module for inclusion proofs, and TentHash Tree for
+//! fingerprinting large data.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tenthash::merkle::{self, Proof};
+//!
+//! let chunks = ["first chunk", "second chunk", "third chunk"];
+//! let leaves = chunks.map(merkle::leaf_digest);
+//! let root = merkle::root(&leaves);
+//!
+//! // Prove that the second chunk is part of the tree.
+//! let proof = Proof::generate(&leaves, 1);
+//! assert!(proof.verify_data("second chunk", &root));
+//! assert!(!proof.verify_data("another chunk", &root));
+//!
+//! // Proofs have a stable byte encoding, for sending them elsewhere.
+//! let mut buf = [0u8; merkle::MAX_ENCODED_PROOF_LEN];
+//! let bytes = proof.encode(&mut buf);
+//! assert_eq!(Proof::decode(bytes), Ok(proof));
+//! ```
+
+use core::fmt;
+
+use crate::{tree::split_point, TentHash, DIGEST_SIZE};
+
+/// The prefix byte of leaf digests.
+const LEAF_PREFIX: u8 = 0x00;
+
+/// The prefix byte of inner node digests.
+const NODE_PREFIX: u8 = 0x01;
+
+/// The maximum depth of a tree, and thus the maximum number of digests in a
+/// proof.  Leaf counts are `u64`s, so this can't be exceeded.
+const MAX_DEPTH: usize = 64;
+
+/// The current version of the encoded proof format.
+const PROOF_FORMAT_VERSION: u8 = 1;
+
+/// The size of the fixed part of an encoded proof.
+const PROOF_HEADER_LEN: usize = 1 + 8 + 8 + 1;
+
+/// The maximum length of an encoded [`Proof`], in bytes.
+pub const MAX_ENCODED_PROOF_LEN: usize = PROOF_HEADER_LEN + MAX_DEPTH * DIGEST_SIZE;
+
+/// Computes the digest of a leaf from its data.
+pub fn leaf_digest(data: impl AsRef<[u8]>) -> [u8; DIGEST_SIZE] {
+    let mut hasher = TentHash::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Computes the digest of an inner node from the digests of its children.
+pub fn node_digest(left: &[u8; DIGEST_SIZE], right: &[u8; DIGEST_SIZE]) -> [u8; DIGEST_SIZE] {
+    let mut hasher = TentHash::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+/// Computes the root digest of a tree from the digests of its leaves.
+///
+/// The leaf digests must have been computed with [`leaf_digest()`].
+pub fn root(leaves: &[[u8; DIGEST_SIZE]]) -> [u8; DIGEST_SIZE] {
+    if leaves.is_empty() {
+        crate::hash([])
+    } else {
+        subtree_root(leaves, |leaf| *leaf)
+    }
+}
+
+/// Computes the root digest of a tree from the data of its leaves.
+///
+/// This is equivalent to computing the [`leaf_digest()`] of each item of
+/// `data` and passing them to [`root()`], but without needing to store the
+/// leaf digests.
+pub fn root_of_data<T: AsRef<[u8]>>(data: &[T]) -> [u8; DIGEST_SIZE] {
+    if data.is_empty() {
+        crate::hash([])
+    } else {
+        subtree_root(data, |item| leaf_digest(item))
+    }
+}
+
+/// Computes the root of a non-empty subtree.
+fn subtree_root<T>(
+    leaves: &[T],
+    leaf_digest: impl Fn(&T) -> [u8; DIGEST_SIZE] + Copy,
+) -> [u8; DIGEST_SIZE] {
+    if leaves.len() == 1 {
+        return leaf_digest(&leaves[0]);
+    }
+    let k = split_point(leaves.len() as u64) as usize;
+    node_digest(
+        &subtree_root(&leaves[..k], leaf_digest),
+        &subtree_root(&leaves[k..], leaf_digest),
+    )
+}
+
+/// Returns the number of digests in the proof for leaf `index` of a tree
+/// with `leaf_count` leaves.
+fn proof_len(mut index: u64, mut leaf_count: u64) -> usize {
+    let mut len = 0;
+    while leaf_count > 1 {
+        let k = split_point(leaf_count);
+        if index < k {
+            leaf_count = k;
+        } else {
+            index -= k;
+            leaf_count -= k;
+        }
+        len += 1;
+    }
+    len
+}
+
+/// An inclusion proof, showing that a leaf is part of a tree.
+///
+/// Contains the position of the leaf and the digests of the sibling subtrees
+/// on the path from the leaf to the root, ordered from the leaf up.  This is
+/// the "audit path" of RFC 9162.
+///
+/// # Encoding
+///
+/// [`encode()`](Proof::encode) and [`decode()`](Proof::decode) use the
+/// following fixed, platform-independent format, with all integers little
+/// endian:
+///
+/// | Bytes      | Contents                                      |
+/// |------------|-----------------------------------------------|
+/// | `0`        | Format version, currently `1`.                |
+/// | `1..9`     | The index of the leaf, as a `u64`.            |
+/// | `9..17`    | The number of leaves in the tree, as a `u64`. |
+/// | `17`       | The number of sibling digests, `n`.           |
+/// | `18..`     | The `n` sibling digests, 20 bytes each.       |
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Proof {
+    index: u64,
+    leaf_count: u64,
+    // Unused entries are always zero, so the derived traits work as expected.
+    siblings: [[u8; DIGEST_SIZE]; MAX_DEPTH],
+    len: u8,
+}
+
+impl Proof {
+    /// Generates the proof for leaf `index` of the tree with the given leaf
+    /// digests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn generate(leaves: &[[u8; DIGEST_SIZE]], index: usize) -> Proof {
+        assert!(
+            index < leaves.len(),
+            "leaf index {} out of bounds for {} leaves",
+            index,
+            leaves.len()
+        );
+
+        let mut proof = Proof {
+            index: index as u64,
+            leaf_count: leaves.len() as u64,
+            siblings: [[0; DIGEST_SIZE]; MAX_DEPTH],
+            len: proof_len(index as u64, leaves.len() as u64) as u8,
+        };
+
+        // Walk down from the root, filling in the siblings from the end.
+        let mut leaves = leaves;
+        let mut index = index;
+        for i in (0..proof.len as usize).rev() {
+            let k = split_point(leaves.len() as u64) as usize;
+            if index < k {
+                proof.siblings[i] = subtree_root(&leaves[k..], |leaf| *leaf);
+                leaves = &leaves[..k];
+            } else {
+                proof.siblings[i] = subtree_root(&leaves[..k], |leaf| *leaf);
+                leaves = &leaves[k..];
+                index -= k;
+            }
+        }
+
+        proof
+    }
+
+    /// Returns the index of the leaf that the proof is for.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the number of leaves in the tree.
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Returns the digests of the sibling subtrees, ordered from the leaf up.
+    pub fn siblings(&self) -> &[[u8; DIGEST_SIZE]] {
+        &self.siblings[..self.len as usize]
+    }
+
+    /// Computes the root digest implied by the proof for the given leaf
+    /// digest.
+    pub fn root_for(&self, leaf: &[u8; DIGEST_SIZE]) -> [u8; DIGEST_SIZE] {
+        // This is the algorithm of RFC 9162 section 2.1.3.2, but without the
+        // consistency checks, which `Proof`'s invariants make unnecessary.
+        let mut node_index = self.index;
+        let mut last_index = self.leaf_count - 1;
+        let mut digest = *leaf;
+        for sibling in self.siblings() {
+            if node_index & 1 == 1 || node_index == last_index {
+                digest = node_digest(sibling, &digest);
+                while node_index & 1 == 0 && node_index != 0 {
+                    node_index >>= 1;
+                    last_index >>= 1;
+                }
+            } else {
+                digest = node_digest(&digest, sibling);
+            }
+            node_index >>= 1;
+            last_index >>= 1;
+        }
+        digest
+    }
+
+    /// Checks that the leaf with the given digest is part of the tree with
+    /// the given root, at the position the proof is for.
+    pub fn verify(&self, leaf: &[u8; DIGEST_SIZE], root: &[u8; DIGEST_SIZE]) -> bool {
+        self.root_for(leaf) == *root
+    }
+
+    /// Like [`verify()`](Proof::verify), but takes the leaf's data rather
+    /// than its digest.
+    pub fn verify_data(&self, data: impl AsRef<[u8]>, root: &[u8; DIGEST_SIZE]) -> bool {
+        self.verify(&leaf_digest(data), root)
+    }
+
+    /// Encodes the proof as bytes, and returns them as a slice of `out`.
+    ///
+    /// See the [type documentation](Proof#encoding) for the format.
+    pub fn encode<'a>(&self, out: &'a mut [u8; MAX_ENCODED_PROOF_LEN]) -> &'a [u8] {
+        out[0] = PROOF_FORMAT_VERSION;
+        out[1..9].copy_from_slice(&self.index.to_le_bytes());
+        out[9..17].copy_from_slice(&self.leaf_count.to_le_bytes());
+        out[17] = self.len;
+        for (sibling, bytes) in self
+            .siblings()
+            .iter()
+            .zip(out[PROOF_HEADER_LEN..].chunks_exact_mut(DIGEST_SIZE))
+        {
+            bytes.copy_from_slice(sibling);
+        }
+        &out[..(PROOF_HEADER_LEN + self.len as usize * DIGEST_SIZE)]
+    }
+
+    /// Decodes a proof previously encoded with [`encode()`](Proof::encode).
+    ///
+    /// Returns an error if the bytes aren't a valid encoded proof.
+    pub fn decode(bytes: &[u8]) -> Result<Proof, ProofError> {
+        if bytes.len() < PROOF_HEADER_LEN {
+            return Err(ProofError::InvalidLength(bytes.len()));
+        }
+        if bytes[0] != PROOF_FORMAT_VERSION {
+            return Err(ProofError::UnsupportedVersion(bytes[0]));
+        }
+
+        let index = u64::from_le_bytes(bytes[1..9].try_into().unwrap());
+        let leaf_count = u64::from_le_bytes(bytes[9..17].try_into().unwrap());
+        if index >= leaf_count {
+            return Err(ProofError::IndexOutOfBounds);
+        }
+        let len = proof_len(index, leaf_count);
+        if bytes[17] as usize != len {
+            return Err(ProofError::WrongSiblingCount(bytes[17]));
+        }
+        if bytes.len() != PROOF_HEADER_LEN + len * DIGEST_SIZE {
+            return Err(ProofError::InvalidLength(bytes.len()));
+        }
+
+        let mut proof = Proof {
+            index,
+            leaf_count,
+            siblings: [[0; DIGEST_SIZE]; MAX_DEPTH],
+            len: len as u8,
+        };
+        for (sibling, bytes) in proof
+            .siblings
+            .iter_mut()
+            .zip(bytes[PROOF_HEADER_LEN..].chunks_exact(DIGEST_SIZE))
+        {
+            sibling.copy_from_slice(bytes);
+        }
+
+        Ok(proof)
+    }
+}
+
+impl fmt::Debug for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Proof")
+            .field("index", &self.index)
+            .field("leaf_count", &self.leaf_count)
+            .field("siblings", &self.siblings())
+            .finish()
+    }
+}
+
+/// The error returned when decoding an invalid proof.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    /// The encoded proof has the wrong size.  Contains the actual size.
+    InvalidLength(usize),
+
+    /// The proof is from an unknown format version.  Contains that version.
+    UnsupportedVersion(u8),
+
+    /// The leaf index isn't less than the leaf count.
+    IndexOutOfBounds,
+
+    /// The number of sibling digests doesn't match the leaf's position in
+    /// the tree.  Contains that number.
+    WrongSiblingCount(u8),
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ProofError::InvalidLength(len) => {
+                write!(f, "invalid encoded proof length: {} bytes", len)
+            }
+            ProofError::UnsupportedVersion(version) => {
+                write!(f, "unsupported proof format version: {}", version)
+            }
+            ProofError::IndexOutOfBounds => f.write_str("proof leaf index is out of bounds"),
+            ProofError::WrongSiblingCount(count) => {
+                write!(f, "wrong number of sibling digests in proof: {}", count)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProofError {}
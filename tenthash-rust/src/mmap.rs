@@ -0,0 +1,151 @@
+//! Memory-mapped file hashing, available with the `mmap` feature.
+//!
+//! The usual problem with memory-mapped files is that another process can
+//! modify or truncate the file while it's mapped.  Modification breaks the
+//! assumptions that Rust makes about the contents of `&[u8]` slices, so the
+//! mapped memory is never accessed through a slice, only through volatile
+//! reads.  Truncation can't be guarded against that way, which is why
+//! [`hash_file_mmap()`] is `unsafe`.
+//!
+//! Checking the file's length before or while reading from the mapping
+//! doesn't help either, because the file can still be truncated between the
+//! check and the read.  Handling truncation without UB would mean catching
+//! `SIGBUS` (or the equivalent on other platforms) during every read, which
+//! is well outside what this crate can do soundly.  So rather than claiming
+//! to handle it, that's left as an explicit obligation of the caller.
+
+use std::{fs::File, io, path::Path};
+
+use memmap2::{MmapOptions, MmapRaw};
+
+use crate::{mix_state, TentHash, BLOCK_SIZE, DIGEST_SIZE};
+
+/// Computes TentHash for the contents of a file, using memory mapping.
+///
+/// For large files this is typically faster than
+/// [`hash_file()`](crate::hash_file), which reads the file into a buffer.
+/// The sequential access pattern is also communicated to the operating
+/// system where supported (`madvise(MADV_SEQUENTIAL)` on Unix), so that it
+/// can read ahead aggressively.
+///
+/// Files that can't be meaningfully mapped, such as empty files, pipes, and
+/// other special files, are hashed with buffered reading instead.  Likewise if
+/// mapping fails for any other reason.  Either way, the digest is the same
+/// as [`hash_file()`](crate::hash_file)'s.
+///
+/// Available with the `mmap` feature.
+///
+/// # Concurrent modification
+///
+/// The file is hashed as it is when mapped: if it grows while being hashed,
+/// the additional data is ignored.  If it's *modified* while being hashed,
+/// the resulting digest is meaningless, but everything is otherwise
+/// well-behaved.
+///
+/// # Safety
+///
+/// The file must not be truncated while it's being hashed.  Reading a part
+/// of a mapped file that no longer exists is not something Rust can guard
+/// against: on some platforms (including Linux and macOS) it raises a
+/// `SIGBUS` signal, which terminates the process by default.
+///
+/// So only use this on files that nothing else will truncate in the
+/// meantime, e.g. because they're locked or are known to be immutable.
+/// Otherwise use [`hash_file()`](crate::hash_file) instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// // SAFETY: nothing else touches this file while it's being hashed.
+/// let hash = unsafe { tenthash::hash_file_mmap("some/big/file.bin") }.unwrap();
+/// ```
+#[allow(unsafe_code)]
+pub unsafe fn hash_file_mmap(path: impl AsRef<Path>) -> io::Result<[u8; DIGEST_SIZE]> {
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+
+    if !metadata.is_file() || metadata.len() == 0 || metadata.len() > usize::MAX as u64 {
+        return hash_buffered(file);
+    }
+    let map = match MmapOptions::new().map_raw_read_only(&file) {
+        Ok(map) => map,
+        Err(_) => return hash_buffered(file),
+    };
+    #[cfg(unix)]
+    let _ = map.advise(memmap2::Advice::Sequential);
+
+    // Hash the full blocks straight from the mapping.
+    let mut hasher = TentHash::new();
+    let blocks_len = map.len() - map.len() % BLOCK_SIZE;
+    // SAFETY: the caller guarantees that the file isn't truncated.
+    unsafe { hash_blocks(&mut hasher, &map, blocks_len) };
+
+    // Hash the remaining partial block.
+    let mut tail = [0u8; BLOCK_SIZE];
+    let tail_len = map.len() - blocks_len;
+    for (i, byte) in tail[..tail_len].iter_mut().enumerate() {
+        // SAFETY: the caller guarantees that the file isn't truncated.
+        *byte = unsafe { read_byte(&map, blocks_len + i) };
+    }
+    hasher.update(&tail[..tail_len]);
+
+    Ok(hasher.finalize())
+}
+
+fn hash_buffered(file: File) -> io::Result<[u8; DIGEST_SIZE]> {
+    let mut hasher = TentHash::new();
+    hasher.update_reader(file)?;
+    Ok(hasher.finalize())
+}
+
+/// Hashes the first `len` bytes of `map` as full blocks, using volatile
+/// reads.
+///
+/// The hasher must not have any buffered data, and `len` must be a multiple
+/// of the block size.
+///
+/// # Safety
+///
+/// The mapped file must not be truncated to less than `len` bytes while
+/// this runs.
+#[allow(unsafe_code)]
+unsafe fn hash_blocks(hasher: &mut TentHash, map: &MmapRaw, len: usize) {
+    assert!(hasher.buf_length == 0);
+    assert!(len % BLOCK_SIZE == 0);
+    assert!(len <= map.len());
+    let words = map.as_ptr().cast::<u64>();
+
+    for i in (0..(len / 8)).step_by(4) {
+        // SAFETY: the reads are within the mapping as checked above, and are
+        // aligned because mappings are page aligned.  They're also within the
+        // file, as guaranteed by our caller.
+        let block = unsafe {
+            [
+                words.add(i).read_volatile(),
+                words.add(i + 1).read_volatile(),
+                words.add(i + 2).read_volatile(),
+                words.add(i + 3).read_volatile(),
+            ]
+        };
+        for (state, word) in hasher.state.iter_mut().zip(block) {
+            *state ^= u64::from_le(word);
+        }
+        mix_state(&mut hasher.state);
+    }
+
+    hasher.message_length += len as u64;
+}
+
+/// Reads the byte at `offset` in `map`, using a volatile read.
+///
+/// # Safety
+///
+/// The mapped file must not be truncated to `offset` bytes or less while
+/// this runs.
+#[allow(unsafe_code)]
+unsafe fn read_byte(map: &MmapRaw, offset: usize) -> u8 {
+    assert!(offset < map.len());
+    // SAFETY: the read is within the mapping as checked above, and within the
+    // file as guaranteed by our caller.
+    unsafe { map.as_ptr().add(offset).read_volatile() }
+}
@@ -0,0 +1,494 @@
+//! Hashing of multiple independent messages at once.
+//!
+//! TentHash's block loop is inherently serial within a single message.  But
+//! when hashing many independent messages, several hash states can be
+//! computed side-by-side, with each state occupying a SIMD lane.  This makes a
+//! big difference when hashing lots of small messages.
+//!
+//! The SIMD code paths are selected at runtime based on the CPU's supported
+//! features, which requires the `std` feature.  Currently AVX2 on x86-64 is
+//! supported, as well as AVX-512 with the `avx512` feature.  In all other cases (including `no_std` builds) each
+//! message is simply hashed in turn with the normal scalar code, because the
+//! lane-parallel code is slower than that without real SIMD support.
+//!
+//! Either way, the digests are always bit-identical to those of
+//! [`hash()`](crate::hash).
+
+// Explicit lane indexing is clearer here, and is what the vectorizer expects.
+#![allow(clippy::needless_range_loop)]
+
+use crate::DIGEST_SIZE;
+
+#[cfg(feature = "std")]
+use crate::BLOCK_SIZE;
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+use crate::{mix_state, INITIAL_STATE, ROTATIONS};
+
+/// Computes TentHash for four independent messages at once.
+///
+/// Equivalent to calling [`hash()`](crate::hash) on each message, but faster
+/// on supported hardware.  Performs best when the messages are similar in
+/// length.
+///
+/// # Example
+///
+/// ```rust
+/// let digests = tenthash::hash_4x(["one", "two", "three", "four"]);
+///
+/// assert_eq!(digests[2], tenthash::hash("three"));
+/// ```
+pub fn hash_4x<T: AsRef<[u8]>>(messages: [T; 4]) -> [[u8; DIGEST_SIZE]; 4] {
+    hash_group(core::array::from_fn(|i| messages[i].as_ref()))
+}
+
+/// Computes TentHash for eight independent messages at once.
+///
+/// Equivalent to calling [`hash()`](crate::hash) on each message, but faster
+/// on supported hardware.  Performs best when the messages are similar in
+/// length.
+///
+/// # Example
+///
+/// ```rust
+/// let animals = ["ant", "bee", "cat", "dog", "eel", "fox", "gnu", "hen"];
+/// let digests = tenthash::hash_8x(animals);
+///
+/// assert_eq!(digests[5], tenthash::hash("fox"));
+/// ```
+pub fn hash_8x<T: AsRef<[u8]>>(messages: [T; 8]) -> [[u8; DIGEST_SIZE]; 8] {
+    hash_group(core::array::from_fn(|i| messages[i].as_ref()))
+}
+
+/// Computes TentHash for any number of independent messages, writing the
+/// digests to `digests`.
+///
+/// Equivalent to calling [`hash()`](crate::hash) on each message, but faster
+/// on supported hardware.  Messages are processed in groups of consecutive
+/// messages, so this performs best when neighboring messages are similar in
+/// length.
+///
+/// # Panics
+///
+/// Panics if `messages` and `digests` have different lengths.
+pub fn hash_many_into<T: AsRef<[u8]>>(messages: &[T], digests: &mut [[u8; DIGEST_SIZE]]) {
+    assert_eq!(
+        messages.len(),
+        digests.len(),
+        "`messages` and `digests` must have the same length"
+    );
+
+    let mut message_groups = messages.chunks_exact(GROUP_SIZE);
+    let mut digest_groups = digests.chunks_exact_mut(GROUP_SIZE);
+    for (group, out) in (&mut message_groups).zip(&mut digest_groups) {
+        let group: [&[u8]; GROUP_SIZE] = core::array::from_fn(|i| group[i].as_ref());
+        out.copy_from_slice(&hash_group(group));
+    }
+
+    for (message, out) in message_groups
+        .remainder()
+        .iter()
+        .zip(digest_groups.into_remainder())
+    {
+        *out = crate::hash(message);
+    }
+}
+
+/// Computes TentHash for any number of independent messages.
+///
+/// Equivalent to calling [`hash()`](crate::hash) on each message, but faster
+/// on supported hardware.  Messages are grouped by length internally, so
+/// unlike [`hash_many_into()`] this performs well regardless of message
+/// order.
+///
+/// Available with the `std` feature.
+///
+/// # Example
+///
+/// ```rust
+/// let records: Vec<Vec<u8>> = (0..100u32).map(|i| i.to_le_bytes().to_vec()).collect();
+/// let digests = tenthash::hash_many(&records);
+///
+/// assert_eq!(digests[42], tenthash::hash(42u32.to_le_bytes()));
+/// ```
+#[cfg(feature = "std")]
+pub fn hash_many<T: AsRef<[u8]>>(messages: &[T]) -> std::vec::Vec<[u8; DIGEST_SIZE]> {
+    use std::vec::Vec;
+
+    // Sort by block count, so that lanes finish at about the same time.
+    let mut order: Vec<usize> = (0..messages.len()).collect();
+    order.sort_by_key(|&i| block_count(messages[i].as_ref()));
+
+    let mut digests = std::vec![[0u8; DIGEST_SIZE]; messages.len()];
+    let mut groups = order.chunks_exact(GROUP_SIZE);
+    for indices in &mut groups {
+        let group: [&[u8]; GROUP_SIZE] = core::array::from_fn(|i| messages[indices[i]].as_ref());
+        let group_digests = hash_group(group);
+        for (&i, digest) in indices.iter().zip(group_digests.iter()) {
+            digests[i] = *digest;
+        }
+    }
+    for &i in groups.remainder() {
+        digests[i] = crate::hash(&messages[i]);
+    }
+
+    digests
+}
+
+/// The number of messages hashed together when hashing arbitrary numbers of
+/// messages.
+const GROUP_SIZE: usize = 8;
+
+/// The number of blocks (including a partial final block) in a message.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn block_count(message: &[u8]) -> usize {
+    message.len().div_ceil(BLOCK_SIZE)
+}
+
+/// Hashes a group of `N` messages with the fastest code path available on
+/// the current CPU.
+#[inline]
+fn hash_group<const N: usize>(messages: [&[u8]; N]) -> [[u8; DIGEST_SIZE]; N] {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    if let Some(digests) = x86_64::hash_group(messages) {
+        return digests;
+    }
+
+    messages.map(crate::hash)
+}
+
+/// Loads the block starting at `offset` as four little-endian words,
+/// zero-padding it if it extends past the end of `data`.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[inline(always)]
+fn load_block(data: &[u8], offset: usize) -> [u64; 4] {
+    let read = |block: &[u8]| -> [u64; 4] {
+        core::array::from_fn(|i| {
+            u64::from_le_bytes(block[(i * 8)..(i * 8 + 8)].try_into().unwrap())
+        })
+    };
+
+    if let Some(block) = data.get(offset..(offset + BLOCK_SIZE)) {
+        read(block)
+    } else {
+        let mut buffer = [0u8; BLOCK_SIZE];
+        let block = &data[offset..];
+        buffer[..block.len()].copy_from_slice(block);
+        read(&buffer)
+    }
+}
+
+/// The operations TentHash needs on vectors of `N` 64-bit lanes.
+///
+/// Implementors are values rather than just types so that they can serve as
+/// proof that the CPU supports the instructions they use.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+trait Lanes<const N: usize>: Copy {
+    type Vector: Copy;
+
+    fn load(self, words: [u64; N]) -> Self::Vector;
+    fn store(self, v: Self::Vector) -> [u64; N];
+    fn wrapping_add(self, a: Self::Vector, b: Self::Vector) -> Self::Vector;
+    fn xor(self, a: Self::Vector, b: Self::Vector) -> Self::Vector;
+    fn rotate_left(self, v: Self::Vector, n: u32) -> Self::Vector;
+}
+
+/// Plain arrays, for the compiler to auto-vectorize.
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+#[derive(Copy, Clone)]
+struct Portable;
+
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+impl<const N: usize> Lanes<N> for Portable {
+    type Vector = [u64; N];
+
+    #[inline(always)]
+    fn load(self, words: [u64; N]) -> [u64; N] {
+        words
+    }
+
+    #[inline(always)]
+    fn store(self, v: [u64; N]) -> [u64; N] {
+        v
+    }
+
+    #[inline(always)]
+    fn wrapping_add(self, mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        for lane in 0..N {
+            a[lane] = a[lane].wrapping_add(b[lane]);
+        }
+        a
+    }
+
+    #[inline(always)]
+    fn xor(self, mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        for lane in 0..N {
+            a[lane] ^= b[lane];
+        }
+        a
+    }
+
+    #[inline(always)]
+    fn rotate_left(self, mut v: [u64; N], n: u32) -> [u64; N] {
+        for lane in 0..N {
+            v[lane] = v[lane].rotate_left(n);
+        }
+        v
+    }
+}
+
+/// Hashes `N` messages in parallel lanes.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[inline(always)]
+fn hash_lanes<L: Lanes<N>, const N: usize>(
+    lanes: L,
+    messages: [&[u8]; N],
+) -> [[u8; DIGEST_SIZE]; N] {
+    // The lanes' hash states, with each word of the state being a vector
+    // across lanes.
+    let mut state = INITIAL_STATE.map(|word| lanes.load([word; N]));
+
+    // Process the blocks that all messages have in lock step.
+    let shared_blocks = messages.iter().map(|m| block_count(m)).min().unwrap_or(0);
+    for block_index in 0..shared_blocks {
+        let offset = block_index * BLOCK_SIZE;
+        let blocks: [[u64; 4]; N] = core::array::from_fn(|lane| load_block(messages[lane], offset));
+        for word in 0..4 {
+            let block_words = lanes.load(core::array::from_fn(|lane| blocks[lane][word]));
+            state[word] = lanes.xor(state[word], block_words);
+        }
+        mix_lanes(lanes, &mut state);
+    }
+
+    // Finish off the remaining blocks of each message individually.
+    if messages.iter().any(|m| block_count(m) > shared_blocks) {
+        let mut words = state.map(|v| lanes.store(v));
+        for (lane, message) in messages.iter().enumerate() {
+            let remaining = &message[(shared_blocks * BLOCK_SIZE).min(message.len())..];
+            let mut lane_state = [
+                words[0][lane],
+                words[1][lane],
+                words[2][lane],
+                words[3][lane],
+            ];
+            for block_index in 0..block_count(remaining) {
+                let block = load_block(remaining, block_index * BLOCK_SIZE);
+                for word in 0..4 {
+                    lane_state[word] ^= block[word];
+                }
+                mix_state(&mut lane_state);
+            }
+            for word in 0..4 {
+                words[word][lane] = lane_state[word];
+            }
+        }
+        state = words.map(|w| lanes.load(w));
+    }
+
+    // Incorporate the message lengths (in bits) and do the final mixing.
+    let lengths = lanes.load(core::array::from_fn(|lane| messages[lane].len() as u64 * 8));
+    state[0] = lanes.xor(state[0], lengths);
+    mix_lanes(lanes, &mut state);
+    mix_lanes(lanes, &mut state);
+
+    // Get the digests as byte arrays.
+    let words = state.map(|v| lanes.store(v));
+    core::array::from_fn(|lane| {
+        let mut digest = [0u8; DIGEST_SIZE];
+        digest[0..8].copy_from_slice(&words[0][lane].to_le_bytes());
+        digest[8..16].copy_from_slice(&words[1][lane].to_le_bytes());
+        digest[16..20].copy_from_slice(&words[2][lane].to_le_bytes()[0..4]);
+        digest
+    })
+}
+
+/// The lane-parallel equivalent of `mix_state()`.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[inline(always)]
+fn mix_lanes<L: Lanes<N>, const N: usize>(lanes: L, state: &mut [L::Vector; 4]) {
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    for rot_pair in ROTATIONS.iter() {
+        a = lanes.wrapping_add(a, c);
+        c = lanes.xor(lanes.rotate_left(c, rot_pair[0]), a);
+        b = lanes.wrapping_add(b, d);
+        d = lanes.xor(lanes.rotate_left(d, rot_pair[1]), b);
+
+        core::mem::swap(&mut a, &mut b);
+    }
+
+    *state = [a, b, c, d];
+}
+
+/// x86-64 SIMD code paths.
+///
+/// Calling functions compiled for CPU features that might not be present is
+/// `unsafe`, so this is the one place in the crate that needs `unsafe` code
+/// for SIMD.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[allow(unsafe_code)]
+mod x86_64 {
+    use super::hash_lanes;
+    #[cfg(feature = "avx512")]
+    use super::Portable;
+    use crate::DIGEST_SIZE;
+
+    use avx2::Avx2;
+
+    /// Hashes a group of `N` messages with SIMD, if the CPU supports it.
+    ///
+    /// `N` must be a multiple of four.
+    #[inline]
+    pub(super) fn hash_group<const N: usize>(
+        messages: [&[u8]; N],
+    ) -> Option<[[u8; DIGEST_SIZE]; N]> {
+        debug_assert!(N % 4 == 0);
+
+        #[cfg(feature = "avx512")]
+        if std::is_x86_feature_detected!("avx512f") {
+            // SAFETY: we just checked that the CPU supports AVX-512F.
+            return Some(unsafe { hash_avx512(messages) });
+        }
+
+        if let Some(avx2) = Avx2::detect() {
+            let mut digests = [[0u8; DIGEST_SIZE]; N];
+            for (group, out) in messages.chunks_exact(4).zip(digests.chunks_exact_mut(4)) {
+                // SAFETY: having an `Avx2` means the CPU supports AVX2.
+                out.copy_from_slice(&unsafe { hash_avx2(avx2, group.try_into().unwrap()) });
+            }
+            Some(digests)
+        } else {
+            None
+        }
+    }
+
+    /// AVX-512 has vector rotation instructions, so the compiler does a good
+    /// job of auto-vectorizing the portable code.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support AVX-512F.
+    #[cfg(feature = "avx512")]
+    #[target_feature(enable = "avx512f")]
+    unsafe fn hash_avx512<const N: usize>(messages: [&[u8]; N]) -> [[u8; DIGEST_SIZE]; N] {
+        hash_lanes(Portable, messages)
+    }
+
+    /// AVX2 doesn't have vector rotation, and the compiler falls back to
+    /// scalar code when auto-vectorizing, so we use intrinsics instead.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support AVX2, which `avx2` guarantees.  This is only
+    /// `unsafe` because of `#[target_feature]`.
+    #[target_feature(enable = "avx2")]
+    unsafe fn hash_avx2(avx2: Avx2, messages: [&[u8]; 4]) -> [[u8; DIGEST_SIZE]; 4] {
+        hash_lanes(avx2, messages)
+    }
+
+    /// In its own module so that `Avx2::detect()` is the only way to get an
+    /// `Avx2`.
+    mod avx2 {
+        use core::arch::x86_64::*;
+
+        use crate::multi::Lanes;
+
+        /// Four-lane operations with AVX2 intrinsics.
+        ///
+        /// An `Avx2` can only be obtained when the CPU supports AVX2, which is
+        /// what makes its (safe) methods sound.
+        #[derive(Copy, Clone)]
+        pub(in crate::multi) struct Avx2 {
+            _private: (),
+        }
+
+        impl Avx2 {
+            /// Returns an `Avx2` if the CPU supports AVX2.
+            #[inline]
+            pub(in crate::multi) fn detect() -> Option<Avx2> {
+                if std::is_x86_feature_detected!("avx2") {
+                    Some(Avx2 { _private: () })
+                } else {
+                    None
+                }
+            }
+        }
+
+        // SAFETY (for all methods): `self` proves that the CPU supports AVX2.
+        impl Lanes<4> for Avx2 {
+            type Vector = __m256i;
+
+            #[inline(always)]
+            fn load(self, words: [u64; 4]) -> __m256i {
+                let [w0, w1, w2, w3] = words.map(|w| w as i64);
+                unsafe { _mm256_set_epi64x(w3, w2, w1, w0) }
+            }
+
+            #[inline(always)]
+            fn store(self, v: __m256i) -> [u64; 4] {
+                unsafe {
+                    [
+                        _mm256_extract_epi64::<0>(v) as u64,
+                        _mm256_extract_epi64::<1>(v) as u64,
+                        _mm256_extract_epi64::<2>(v) as u64,
+                        _mm256_extract_epi64::<3>(v) as u64,
+                    ]
+                }
+            }
+
+            #[inline(always)]
+            fn wrapping_add(self, a: __m256i, b: __m256i) -> __m256i {
+                unsafe { _mm256_add_epi64(a, b) }
+            }
+
+            #[inline(always)]
+            fn xor(self, a: __m256i, b: __m256i) -> __m256i {
+                unsafe { _mm256_xor_si256(a, b) }
+            }
+
+            #[inline(always)]
+            fn rotate_left(self, v: __m256i, n: u32) -> __m256i {
+                // The rotation amounts are constants after inlining, so these
+                // compile to immediate shifts.
+                unsafe {
+                    let left = _mm256_sll_epi64(v, _mm_cvtsi32_si128(n as i32));
+                    let right = _mm256_srl_epi64(v, _mm_cvtsi32_si128(64 - n as i32));
+                    _mm256_or_si256(left, right)
+                }
+            }
+        }
+    }
+
+    // The public API only exercises the fastest code path that the CPU
+    // supports, so test them all directly here.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn test_messages() -> [std::vec::Vec<u8>; 4] {
+            core::array::from_fn(|i| (0..(i * 37)).map(|j| (i + j) as u8).collect())
+        }
+
+        #[test]
+        fn avx2() {
+            let Some(avx2) = Avx2::detect() else {
+                return;
+            };
+            let messages = test_messages();
+            let digests = unsafe { hash_avx2(avx2, core::array::from_fn(|i| &messages[i][..])) };
+            assert_eq!(digests, messages.map(crate::hash));
+        }
+
+        #[cfg(feature = "avx512")]
+        #[test]
+        fn avx512() {
+            if !std::is_x86_feature_detected!("avx512f") {
+                return;
+            }
+            let messages = test_messages();
+            let digests = unsafe { hash_avx512(core::array::from_fn(|i| &messages[i][..])) };
+            assert_eq!(digests, messages.map(crate::hash));
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Self-describing digests, which carry the algorithm that produced them.
+//!
+//! A bare digest is just bytes, and nothing about it says which hash
+//! function it came from or how long it's supposed to be.  A [`Multihash`]
+//! wraps a TentHash digest in one of two self-describing forms:
+//!
+//! - **Binary**, in the [multihash](https://multiformats.io/multihash/)
+//!   format: the hash function code and the digest length, each as an
+//!   unsigned varint, followed by the digest bytes.
+//! - **Text**, as `tenthash:` followed by the digest in lowercase hex, e.g.
+//!   `tenthash:de77f1c134228be1b5b25c941d5102f87f3e6d39`.
+//!
+//! Both the full 160-bit digest and the 128-bit truncation (see
+//! [`DigestExt`]) are supported, and the length is part of both forms.
+//! Parsing is strict, and rejects unknown codes and prefixes, digest lengths
+//! other than 160 and 128 bits, and trailing data.
+//!
+//! Note that TentHash doesn't have a registered multicodec code yet, so
+//! [`CODE`] is from the private use range of the multicodec table.  Treat
+//! binary multihashes as specific to your own systems until then.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tenthash::multihash::Multihash;
+//!
+//! let mh = Multihash::new(tenthash::hash("Hello world!"));
+//!
+//! let mut buf = [0u8; tenthash::multihash::MAX_ENCODED_LEN];
+//! let bytes = mh.encode(&mut buf);
+//! assert_eq!(Multihash::decode(bytes), Ok(mh));
+//!
+//! let text = mh.to_string();
+//! assert!(text.starts_with("tenthash:"));
+//! assert_eq!(text.parse::<Multihash>(), Ok(mh));
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+    encoding::{self, DecodeError},
+    DigestExt, DIGEST_SIZE, TRUNCATED_SIZE,
+};
+
+/// The multicodec code used for TentHash.
+///
+/// This is in the multicodec private use range (`0x300000` to `0x3fffff`).
+pub const CODE: u64 = 0x3000a0;
+
+/// The maximum length of a binary-encoded [`Multihash`], in bytes.
+pub const MAX_ENCODED_LEN: usize = varint_len(CODE) + 1 + DIGEST_SIZE;
+
+/// The prefix of the text form of a [`Multihash`].
+pub const TEXT_PREFIX: &str = "tenthash:";
+
+/// The maximum length of an unsigned varint, per the multiformats spec.
+const MAX_VARINT_LEN: usize = 9;
+
+/// A self-describing TentHash digest, either 160 or 128 bits long.
+///
+/// See the [module documentation](self) for details.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Multihash {
+    // Unused bytes are always zero, so the derived traits work as expected.
+    bytes: [u8; DIGEST_SIZE],
+    len: u8,
+}
+
+impl Multihash {
+    /// Creates a multihash from a full 160-bit digest.
+    pub fn new(digest: impl Into<[u8; DIGEST_SIZE]>) -> Multihash {
+        Multihash {
+            bytes: digest.into(),
+            len: DIGEST_SIZE as u8,
+        }
+    }
+
+    /// Creates a multihash from a digest truncated to 128 bits.
+    ///
+    /// The truncation is done with [`DigestExt::to_16_bytes()`].
+    pub fn new_128(digest: impl DigestExt) -> Multihash {
+        let mut bytes = [0u8; DIGEST_SIZE];
+        bytes[..TRUNCATED_SIZE].copy_from_slice(&digest.to_16_bytes());
+        Multihash {
+            bytes,
+            len: TRUNCATED_SIZE as u8,
+        }
+    }
+
+    /// Returns the digest bytes: 20 bytes for a 160-bit digest, or 16 bytes
+    /// for a 128-bit digest.
+    pub fn digest(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// Returns the size of the digest, in bits.  Either 160 or 128.
+    pub fn bits(&self) -> u32 {
+        self.len as u32 * 8
+    }
+
+    /// Encodes the multihash in the binary multihash format, and returns the
+    /// encoded bytes as a slice of `out`.
+    pub fn encode<'a>(&self, out: &'a mut [u8; MAX_ENCODED_LEN]) -> &'a [u8] {
+        let mut len = write_varint(CODE, out);
+        len += write_varint(self.len as u64, &mut out[len..]);
+        out[len..(len + self.len as usize)].copy_from_slice(self.digest());
+        &out[..(len + self.len as usize)]
+    }
+
+    /// Decodes a multihash from the binary multihash format.
+    ///
+    /// `bytes` must contain exactly one multihash, with no trailing data.
+    pub fn decode(bytes: &[u8]) -> Result<Multihash, MultihashError> {
+        let (code, code_len) = read_varint(bytes)?;
+        if code != CODE {
+            return Err(MultihashError::UnknownCode(code));
+        }
+        let (digest_len, len_len) = read_varint(&bytes[code_len..])?;
+        if digest_len != DIGEST_SIZE as u64 && digest_len != TRUNCATED_SIZE as u64 {
+            return Err(MultihashError::UnsupportedDigestLength(digest_len));
+        }
+
+        let digest = &bytes[(code_len + len_len)..];
+        if digest.len() != digest_len as usize {
+            return Err(MultihashError::InvalidLength(bytes.len()));
+        }
+        let mut mh = Multihash {
+            bytes: [0; DIGEST_SIZE],
+            len: digest_len as u8,
+        };
+        mh.bytes[..digest.len()].copy_from_slice(digest);
+
+        Ok(mh)
+    }
+}
+
+impl From<[u8; DIGEST_SIZE]> for Multihash {
+    fn from(digest: [u8; DIGEST_SIZE]) -> Multihash {
+        Multihash::new(digest)
+    }
+}
+
+impl From<crate::Digest> for Multihash {
+    fn from(digest: crate::Digest) -> Multihash {
+        Multihash::new(digest)
+    }
+}
+
+impl fmt::Debug for Multihash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Multihash({})", self)
+    }
+}
+
+/// Formats the multihash in its text form, `tenthash:<hex>`.
+impl fmt::Display for Multihash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut text = [0u8; TEXT_PREFIX.len() + DIGEST_SIZE * 2];
+        text[..TEXT_PREFIX.len()].copy_from_slice(TEXT_PREFIX.as_bytes());
+        let hex = encoding::encode_hex_bytes(self.digest(), &mut text[TEXT_PREFIX.len()..]);
+        let len = TEXT_PREFIX.len() + hex.len();
+
+        // The text is guaranteed to be ascii, so this can't fail.
+        f.pad(core::str::from_utf8(&text[..len]).unwrap())
+    }
+}
+
+/// Parses a multihash from its text form, `tenthash:<hex>`.
+///
+/// The hex digest must be either 40 digits (160 bits) or 32 digits (128
+/// bits) long.  Both lowercase and uppercase hex digits are accepted.
+impl FromStr for Multihash {
+    type Err = MultihashError;
+
+    fn from_str(text: &str) -> Result<Multihash, MultihashError> {
+        let hex = text
+            .strip_prefix(TEXT_PREFIX)
+            .ok_or(MultihashError::UnknownPrefix)?
+            .as_bytes();
+
+        if hex.len() == TRUNCATED_SIZE * 2 {
+            let digest: [u8; TRUNCATED_SIZE] =
+                encoding::decode_hex_bytes(hex).map_err(MultihashError::InvalidHex)?;
+            let mut bytes = [0u8; DIGEST_SIZE];
+            bytes[..TRUNCATED_SIZE].copy_from_slice(&digest);
+            Ok(Multihash {
+                bytes,
+                len: TRUNCATED_SIZE as u8,
+            })
+        } else {
+            encoding::decode_hex(hex)
+                .map(Multihash::new)
+                .map_err(MultihashError::InvalidHex)
+        }
+    }
+}
+
+/// Returns the number of bytes `n` takes as an unsigned varint.
+const fn varint_len(mut n: u64) -> usize {
+    let mut len = 1;
+    while n >= 0x80 {
+        n >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// Writes `n` as an unsigned varint to the start of `out`, and returns the
+/// number of bytes written.
+fn write_varint(mut n: u64, out: &mut [u8]) -> usize {
+    let mut len = 0;
+    while n >= 0x80 {
+        out[len] = (n as u8) | 0x80;
+        n >>= 7;
+        len += 1;
+    }
+    out[len] = n as u8;
+    len + 1
+}
+
+/// Reads an unsigned varint from the start of `bytes`, and returns it along
+/// with the number of bytes it took.
+///
+/// Per the multiformats spec, the varint must be minimally encoded and at
+/// most nine bytes long.
+fn read_varint(bytes: &[u8]) -> Result<(u64, usize), MultihashError> {
+    let mut n = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        n |= ((byte & 0x7f) as u64) << (i * 7);
+        if byte & 0x80 == 0 {
+            // A trailing zero byte means the varint isn't minimally encoded.
+            if byte == 0 && i > 0 {
+                return Err(MultihashError::InvalidVarint);
+            }
+            return Ok((n, i + 1));
+        }
+    }
+    Err(MultihashError::InvalidVarint)
+}
+
+/// The error returned when decoding or parsing a [`Multihash`] fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MultihashError {
+    /// A varint is truncated, too long, or not minimally encoded.
+    InvalidVarint,
+
+    /// The hash function code isn't TentHash's.  Contains the code.
+    UnknownCode(u64),
+
+    /// The digest length isn't 20 or 16 bytes.  Contains the length.
+    UnsupportedDigestLength(u64),
+
+    /// The input is shorter or longer than the encoded digest length says.
+    /// Contains the actual length of the input.
+    InvalidLength(usize),
+
+    /// The text doesn't start with `tenthash:`.
+    UnknownPrefix,
+
+    /// The hex digest in the text is invalid.
+    InvalidHex(DecodeError),
+}
+
+impl fmt::Display for MultihashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            MultihashError::InvalidVarint => f.write_str("invalid varint in multihash"),
+            MultihashError::UnknownCode(code) => {
+                write!(f, "unknown multihash code: {:#x}", code)
+            }
+            MultihashError::UnsupportedDigestLength(len) => {
+                write!(f, "unsupported multihash digest length: {} bytes", len)
+            }
+            MultihashError::InvalidLength(len) => {
+                write!(f, "invalid multihash length: {} bytes", len)
+            }
+            MultihashError::UnknownPrefix => {
+                write!(f, "multihash text doesn't start with \"{}\"", TEXT_PREFIX)
+            }
+            MultihashError::InvalidHex(err) => write!(f, "invalid multihash text: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MultihashError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MultihashError::InvalidHex(err) => Some(err),
+            _ => None,
+        }
+    }
+}
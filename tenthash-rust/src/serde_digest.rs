@@ -0,0 +1,35 @@
+//! Serde support for plain `[u8; 20]` digests, available with the `serde`
+//! feature.
+//!
+//! Use this with `#[serde(with = "tenthash::serde_digest")]` on `[u8; 20]`
+//! fields to serialize them the same way as [`Digest`]: as a lowercase hex
+//! string in human-readable formats (like JSON), and as 20 bytes in binary
+//! formats.  Serde's default for arrays is a sequence of numbers instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Entry {
+//!     name: String,
+//!     #[serde(with = "tenthash::serde_digest")]
+//!     digest: [u8; 20],
+//! }
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Digest, DIGEST_SIZE};
+
+pub fn serialize<S: Serializer>(
+    digest: &[u8; DIGEST_SIZE],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Digest::from(*digest).serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; DIGEST_SIZE], D::Error> {
+    Digest::deserialize(deserializer).map(Digest::to_bytes)
+}
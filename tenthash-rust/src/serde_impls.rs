@@ -0,0 +1,206 @@
+//! Serde support, available with the `serde` feature.
+
+use core::fmt;
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    encoding,
+    multihash::{self, Multihash},
+    Digest, TentHash, DIGEST_SIZE,
+};
+
+/// Serializes the hasher state in the format of [`TentHash::export_state()`],
+/// as a lowercase hex string in human-readable formats (like JSON), and as
+/// bytes in binary formats.
+impl Serialize for TentHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let state = self.export_state();
+        if serializer.is_human_readable() {
+            serializer.serialize_str(encoding::encode_hex_bytes(
+                &state,
+                &mut [0; TentHash::EXPORTED_STATE_SIZE * 2],
+            ))
+        } else {
+            serializer.serialize_bytes(&state)
+        }
+    }
+}
+
+/// Deserializes the hasher state in the format of
+/// [`TentHash::export_state()`], from a hex string in human-readable formats,
+/// and from bytes in binary formats.
+impl<'de> Deserialize<'de> for TentHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TentHash, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StateVisitor)
+        } else {
+            deserializer.deserialize_bytes(StateVisitor)
+        }
+    }
+}
+
+struct StateVisitor;
+
+impl<'de> Visitor<'de> for StateVisitor {
+    type Value = TentHash;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "exported TentHash state as {} hex digits or {} bytes",
+            TentHash::EXPORTED_STATE_SIZE * 2,
+            TentHash::EXPORTED_STATE_SIZE
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<TentHash, E> {
+        let bytes: [u8; TentHash::EXPORTED_STATE_SIZE] =
+            encoding::decode_hex_bytes(text.as_bytes()).map_err(E::custom)?;
+        self.visit_bytes(&bytes)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<TentHash, E> {
+        TentHash::import_state(bytes).map_err(E::custom)
+    }
+
+    // For formats that represent bytes as a sequence of integers.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TentHash, A::Error> {
+        let mut bytes = [0u8; TentHash::EXPORTED_STATE_SIZE];
+        let mut len = 0;
+        while let Some(byte) = seq.next_element::<u8>()? {
+            if len == bytes.len() {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            bytes[len] = byte;
+            len += 1;
+        }
+        self.visit_bytes(&bytes[..len])
+    }
+}
+
+/// Serializes the digest as a lowercase hex string in human-readable formats
+/// (like JSON), and as 20 bytes in binary formats.
+impl Serialize for Digest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(encoding::encode_hex(
+                self.as_bytes(),
+                &mut [0; encoding::HEX_LEN],
+            ))
+        } else {
+            serializer.serialize_bytes(self.as_bytes())
+        }
+    }
+}
+
+/// Deserializes the digest from a hex string in human-readable formats, and
+/// from 20 bytes in binary formats.
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Digest, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DigestVisitor)
+        } else {
+            deserializer.deserialize_bytes(DigestVisitor)
+        }
+    }
+}
+
+struct DigestVisitor;
+
+impl<'de> Visitor<'de> for DigestVisitor {
+    type Value = Digest;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a TentHash digest as {} hex digits or {} bytes",
+            encoding::HEX_LEN,
+            DIGEST_SIZE
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Digest, E> {
+        text.parse().map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Digest, E> {
+        let bytes: [u8; DIGEST_SIZE] = bytes
+            .try_into()
+            .map_err(|_| E::invalid_length(bytes.len(), &self))?;
+        Ok(Digest::from(bytes))
+    }
+
+    // For formats that represent bytes as a sequence of integers.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Digest, A::Error> {
+        let mut bytes = [0u8; DIGEST_SIZE];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(DIGEST_SIZE + 1, &self));
+        }
+        Ok(Digest::from(bytes))
+    }
+}
+
+/// Serializes the multihash in its text form in human-readable formats, and
+/// in the binary multihash format in binary formats.
+impl Serialize for Multihash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(self.encode(&mut [0; multihash::MAX_ENCODED_LEN]))
+        }
+    }
+}
+
+/// Deserializes the multihash from its text form in human-readable formats,
+/// and from the binary multihash format in binary formats.
+impl<'de> Deserialize<'de> for Multihash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Multihash, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(MultihashVisitor)
+        } else {
+            deserializer.deserialize_bytes(MultihashVisitor)
+        }
+    }
+}
+
+struct MultihashVisitor;
+
+impl<'de> Visitor<'de> for MultihashVisitor {
+    type Value = Multihash;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a TentHash multihash")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Multihash, E> {
+        text.parse().map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Multihash, E> {
+        Multihash::decode(bytes).map_err(E::custom)
+    }
+
+    // For formats that represent bytes as a sequence of integers.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Multihash, A::Error> {
+        let mut bytes = [0u8; multihash::MAX_ENCODED_LEN];
+        let mut len = 0;
+        while let Some(byte) = seq.next_element::<u8>()? {
+            if len == bytes.len() {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            bytes[len] = byte;
+            len += 1;
+        }
+        self.visit_bytes(&bytes[..len])
+    }
+}
@@ -0,0 +1,152 @@
+//! Exporting and importing of hasher state, for checkpointing and resuming
+//! long-running hashes.
+
+use core::fmt;
+
+use crate::{TentHash, BLOCK_SIZE};
+
+/// The current version of the exported state format.
+const STATE_FORMAT_VERSION: u8 = 1;
+
+impl TentHash {
+    /// The size of an exported hasher state, in bytes.
+    pub const EXPORTED_STATE_SIZE: usize = 1 + (8 * 4) + 8 + 1 + BLOCK_SIZE;
+
+    /// Exports the hasher's state as bytes.
+    ///
+    /// The exported state can later be passed to
+    /// [`import_state()`](TentHash::import_state) to get a hasher that picks up
+    /// exactly where this one left off.  This is useful for checkpointing the
+    /// hashing of long data streams, so that it can survive e.g. a process
+    /// restart.
+    ///
+    /// The format is fixed and platform independent (all integers are little
+    /// endian), so exported states can be moved between machines.  It is
+    /// laid out as follows:
+    ///
+    /// | Bytes     | Contents                                             |
+    /// |-----------|------------------------------------------------------|
+    /// | `0`       | Format version, currently `1`.                       |
+    /// | `1..33`   | The four 64-bit words of the hash state.             |
+    /// | `33..41`  | The number of bytes hashed so far, as a `u64`.       |
+    /// | `41`      | The number of bytes in the pending block buffer.     |
+    /// | `42..74`  | The pending block buffer, padded with zeros.         |
+    ///
+    /// Note that the exported state contains up to 32 bytes of the hashed
+    /// data verbatim, so treat it with the same care as the data itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tenthash::TentHash;
+    /// let mut hasher = TentHash::new();
+    /// hasher.update("Hello");
+    /// let checkpoint = hasher.export_state();
+    ///
+    /// // ...later, possibly in another process.
+    /// let mut hasher = TentHash::import_state(&checkpoint).unwrap();
+    /// hasher.update(" world!");
+    ///
+    /// assert_eq!(hasher.finalize(), tenthash::hash("Hello world!"));
+    /// ```
+    pub fn export_state(&self) -> [u8; TentHash::EXPORTED_STATE_SIZE] {
+        let mut bytes = [0u8; TentHash::EXPORTED_STATE_SIZE];
+
+        bytes[0] = STATE_FORMAT_VERSION;
+        for (i, word) in self.state.iter().enumerate() {
+            bytes[(1 + i * 8)..(9 + i * 8)].copy_from_slice(&word.to_le_bytes());
+        }
+        bytes[33..41].copy_from_slice(&self.message_length.to_le_bytes());
+        bytes[41] = self.buf_length as u8;
+        bytes[42..(42 + self.buf_length)].copy_from_slice(&self.buf[..self.buf_length]);
+
+        bytes
+    }
+
+    /// Creates a hasher from a state previously exported with
+    /// [`export_state()`](TentHash::export_state).
+    ///
+    /// Returns an error if the passed bytes aren't a valid exported state.
+    pub fn import_state(bytes: &[u8]) -> Result<TentHash, StateError> {
+        if bytes.len() != TentHash::EXPORTED_STATE_SIZE {
+            return Err(StateError::InvalidLength(bytes.len()));
+        }
+        if bytes[0] != STATE_FORMAT_VERSION {
+            return Err(StateError::UnsupportedVersion(bytes[0]));
+        }
+
+        let read_u64 = |i: usize| u64::from_le_bytes(bytes[i..(i + 8)].try_into().unwrap());
+        let state = [read_u64(1), read_u64(9), read_u64(17), read_u64(25)];
+        let message_length = read_u64(33);
+        let buf_length = bytes[41] as usize;
+
+        if buf_length > BLOCK_SIZE {
+            return Err(StateError::InvalidBufferLength(bytes[41]));
+        }
+        // All bytes before the buffered ones must have been processed as
+        // full blocks.
+        if (buf_length as u64) > message_length
+            || (message_length - buf_length as u64) % BLOCK_SIZE as u64 != 0
+        {
+            return Err(StateError::InconsistentMessageLength);
+        }
+        if bytes[(42 + buf_length)..].iter().any(|&b| b != 0) {
+            return Err(StateError::NonZeroPadding);
+        }
+
+        let mut buf = [0u8; BLOCK_SIZE];
+        buf.copy_from_slice(&bytes[42..]);
+
+        Ok(TentHash {
+            state,
+            buf,
+            buf_length,
+            message_length,
+        })
+    }
+}
+
+/// The error returned when importing an invalid hasher state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StateError {
+    /// The state has the wrong size.  Contains the actual size.
+    InvalidLength(usize),
+
+    /// The state is from an unknown format version.  Contains that version.
+    UnsupportedVersion(u8),
+
+    /// The block buffer length is larger than a block.  Contains that length.
+    InvalidBufferLength(u8),
+
+    /// The message length doesn't agree with the block buffer length.
+    InconsistentMessageLength,
+
+    /// The unused part of the block buffer isn't zeroed.
+    NonZeroPadding,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            StateError::InvalidLength(len) => write!(
+                f,
+                "invalid hasher state length: expected {} bytes, got {}",
+                TentHash::EXPORTED_STATE_SIZE,
+                len
+            ),
+            StateError::UnsupportedVersion(version) => {
+                write!(f, "unsupported hasher state version: {}", version)
+            }
+            StateError::InvalidBufferLength(len) => {
+                write!(f, "invalid hasher state buffer length: {}", len)
+            }
+            StateError::InconsistentMessageLength => {
+                f.write_str("hasher state message length is inconsistent with its buffer length")
+            }
+            StateError::NonZeroPadding => f.write_str("hasher state buffer padding isn't zero"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StateError {}
@@ -0,0 +1,365 @@
+//! TentHash Tree, a parallelizable tree hashing mode built on TentHash.
+//!
+//! Plain TentHash is inherently serial, which caps the throughput of hashing
+//! a single large input at what one core can do.  TentHash Tree instead
+//! splits the input into fixed-size chunks, hashes each chunk independently,
+//! and combines the chunk digests in a binary Merkle tree.  The chunks and
+//! subtrees can then be hashed on as many cores as are available.
+//!
+//! **TentHash Tree is a different hash function than TentHash.**  Its digests
+//! are completely unrelated to the plain TentHash digests of the same data,
+//! and the two must never be mixed up.  In particular, when storing or
+//! transmitting TentHash Tree digests, make sure to label them as such.
+//!
+//! To help with that, TentHash Tree digests are returned as [`TreeDigest`]s
+//! rather than plain byte arrays, so that they can't be accidentally compared
+//! with or stored as plain TentHash digests.
+//!
+//! [`hash()`] and [`TreeHash`] compute TentHash Tree on a single thread,
+//! and work in `no_std` environments.  With the `rayon` feature,
+//! [`hash_parallel()`] computes it using all of the cores available to the
+//! [rayon](https://docs.rs/rayon) thread pool.  All of them produce identical
+//! results.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tenthash::tree;
+//!
+//! let data = vec![42u8; 1 << 20];
+//!
+//! let digest = tree::hash(&data);
+//!
+//! assert_ne!(digest.to_bytes(), tenthash::hash(&data));
+//! ```
+//!
+//! # Specification
+//!
+//! This is version 1 of TentHash Tree.  All hashing below is plain TentHash,
+//! written `TentHash(x)`, and `||` denotes concatenation.
+//!
+//! 1. The input is split into consecutive chunks of [`CHUNK_SIZE`] (65536)
+//!    bytes.  The last chunk may be shorter, but never empty unless the
+//!    input itself is empty, in which case there is a single empty chunk.
+//! 2. Each chunk is hashed into a *leaf digest*:
+//!    `TentHash(chunk || 0x00)`.
+//! 3. The chunks are arranged in a binary tree.  A tree of `n > 1` chunks has
+//!    a left subtree of the first `k` chunks, where `k` is the largest power
+//!    of two less than `n`, and a right subtree of the remaining `n - k`
+//!    chunks.  A tree of one chunk is just that chunk's leaf.  (This is the
+//!    same shape as BLAKE3's tree.)
+//! 4. Each non-leaf node has a *node digest* computed from the digests of its
+//!    left and right subtrees: `TentHash(left || right || 0x01)`.
+//! 5. The final digest is computed from the digest of the top node of the
+//!    tree (which is a leaf digest for single-chunk inputs):
+//!    `TentHash(top || 0x02)`.
+//!
+//! The trailing byte separates the three kinds of hashing from each other,
+//! so that e.g. the digest of a whole input can't coincide with the digest
+//! of a subtree of a larger input.  It's a suffix rather than a prefix so
+//! that the chunk data stays aligned to TentHash's block size.
+//!
+//! # TentHash Tree vs. `merkle`
+//!
+//! The [`merkle`](crate::merkle) module also builds Merkle trees, with the
+//! same shape but different domain separation, so the two are incompatible.
+//! That's deliberate, since they solve different problems.  TentHash Tree is
+//! a hash function for a single byte stream: the chunking is fixed and
+//! internal, and the only output is the digest.  `merkle` is for lists of
+//! separately meaningful items of any size, and follows RFC 9162 so that
+//! it can produce standard inclusion proofs for individual items.  Use
+//! TentHash Tree to fingerprint large data quickly, and `merkle` when you
+//! need to prove that an item is part of a set.
+
+use core::{fmt, str::FromStr};
+
+use crate::{Digest, ParseDigestError, TentHash, BLOCK_SIZE, DIGEST_SIZE};
+
+/// The size of the chunks that the input is split into, in bytes.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+// Domain separation suffixes.
+const LEAF_SUFFIX: u8 = 0x00;
+const NODE_SUFFIX: u8 = 0x01;
+const ROOT_SUFFIX: u8 = 0x02;
+
+/// The maximum number of subtree digests that need to be kept around while
+/// streaming.  Inputs can be at most 2^64 bytes long, which is 2^48 chunks.
+const MAX_DEPTH: usize = 64 - CHUNK_SIZE.trailing_zeros() as usize;
+
+/// Computes TentHash Tree in one go for a contiguous slice of data.
+///
+/// # Example
+///
+/// ```rust
+/// let digest = tenthash::tree::hash("Hello world!");
+///
+/// assert_ne!(digest.to_bytes(), tenthash::hash("Hello world!"));
+/// ```
+pub fn hash(data: impl AsRef<[u8]>) -> TreeDigest {
+    let mut hasher = TreeHash::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Computes TentHash Tree in one go for a contiguous slice of data, using
+/// multiple threads.
+///
+/// The work is distributed over the current [rayon](https://docs.rs/rayon)
+/// thread pool.  The digest is identical to that of [`hash()`].
+///
+/// Available with the `rayon` feature.
+///
+/// # Example
+///
+/// ```rust
+/// let data = vec![42u8; 1 << 24];
+///
+/// assert_eq!(
+///     tenthash::tree::hash_parallel(&data),
+///     tenthash::tree::hash(&data),
+/// );
+/// ```
+#[cfg(feature = "rayon")]
+pub fn hash_parallel(data: impl AsRef<[u8]>) -> TreeDigest {
+    fn subtree(data: &[u8]) -> [u8; DIGEST_SIZE] {
+        if data.len() <= CHUNK_SIZE {
+            return leaf(data);
+        }
+        let (left, right) = data.split_at(left_subtree_len(data.len()));
+        let (left, right) = rayon::join(|| subtree(left), || subtree(right));
+        node(&left, &right)
+    }
+
+    root(&subtree(data.as_ref()))
+}
+
+/// Computes TentHash Tree incrementally, taking input data in pieces.
+///
+/// Like [`TentHash`], the output is unaffected by how the input is split
+/// up.  This computes the tree on a single thread, and uses a fixed amount
+/// of memory regardless of input size.
+///
+/// # Example
+///
+/// ```rust
+/// use tenthash::tree::TreeHash;
+///
+/// let mut hasher = TreeHash::new();
+/// hasher.update("Hello");
+/// hasher.update(" world!");
+///
+/// assert_eq!(hasher.finalize(), tenthash::tree::hash("Hello world!"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreeHash {
+    chunk: TentHash,                       // Hasher for the current chunk.
+    chunk_length: usize,                   // The number of bytes in the current chunk so far.
+    chunk_count: u64,                      // The number of completed chunks.
+    stack: [[u8; DIGEST_SIZE]; MAX_DEPTH], // Digests of completed subtrees.
+    stack_length: usize,                   // The number of digests in `stack`.
+}
+
+impl TreeHash {
+    pub fn new() -> TreeHash {
+        TreeHash {
+            chunk: TentHash::new(),
+            chunk_length: 0,
+            chunk_count: 0,
+            stack: [[0; DIGEST_SIZE]; MAX_DEPTH],
+            stack_length: 0,
+        }
+    }
+
+    /// Appends data to the data stream being hashed.
+    ///
+    /// Call this repeatedly to incrementally append more and more data.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let mut data = data.as_ref();
+
+        while !data.is_empty() {
+            // Only complete a chunk once we know more data follows it, since
+            // the last chunk might need to be the top of the tree.
+            if self.chunk_length == CHUNK_SIZE {
+                let chunk = core::mem::take(&mut self.chunk);
+                self.push_chunk(finalize_leaf(chunk));
+                self.chunk_length = 0;
+            }
+
+            let n = (CHUNK_SIZE - self.chunk_length).min(data.len());
+            self.chunk.update(&data[..n]);
+            self.chunk_length += n;
+            data = &data[n..];
+        }
+    }
+
+    /// Finalizes the hash and returns the digest.
+    pub fn finalize(self) -> TreeDigest {
+        let mut digest = finalize_leaf(self.chunk);
+        for left in self.stack[..self.stack_length].iter().rev() {
+            digest = node(left, &digest);
+        }
+        root(&digest)
+    }
+
+    /// Adds a completed chunk's leaf digest to the tree, merging it with
+    /// completed subtrees of the same size.
+    fn push_chunk(&mut self, mut digest: [u8; DIGEST_SIZE]) {
+        self.chunk_count += 1;
+
+        // The number of trailing zeros in the chunk count is the number of
+        // subtrees that this chunk completes.
+        let mut count = self.chunk_count;
+        while count & 1 == 0 {
+            self.stack_length -= 1;
+            digest = node(&self.stack[self.stack_length], &digest);
+            count >>= 1;
+        }
+
+        self.stack[self.stack_length] = digest;
+        self.stack_length += 1;
+    }
+}
+
+impl Default for TreeHash {
+    fn default() -> TreeHash {
+        TreeHash::new()
+    }
+}
+
+/// A TentHash Tree digest.
+///
+/// This is deliberately a different type than the `[u8; 20]` and [`Digest`]
+/// of plain TentHash, and doesn't compare equal to either.  Otherwise it
+/// works like `Digest`, printing and parsing as hex.
+///
+/// # Example
+///
+/// ```rust
+/// use tenthash::tree::{self, TreeDigest};
+///
+/// let digest = tree::hash("Hello world!");
+/// let text = digest.to_string();
+///
+/// assert_eq!(text.parse::<TreeDigest>(), Ok(digest));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TreeDigest([u8; DIGEST_SIZE]);
+
+impl TreeDigest {
+    /// Creates a digest from its bytes.
+    #[inline(always)]
+    pub const fn from_bytes(bytes: [u8; DIGEST_SIZE]) -> TreeDigest {
+        TreeDigest(bytes)
+    }
+
+    /// Returns a reference to the bytes of the digest.
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8; DIGEST_SIZE] {
+        &self.0
+    }
+
+    /// Returns the bytes of the digest.
+    #[inline(always)]
+    pub const fn to_bytes(self) -> [u8; DIGEST_SIZE] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for TreeDigest {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for TreeDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TreeDigest(")?;
+        fmt::LowerHex::fmt(self, f)?;
+        f.write_str(")")
+    }
+}
+
+/// Formats the digest as lowercase hex.
+impl fmt::Display for TreeDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for TreeDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&Digest::from_bytes(self.0), f)
+    }
+}
+
+impl fmt::UpperHex for TreeDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&Digest::from_bytes(self.0), f)
+    }
+}
+
+/// Parses a digest from 40 hex digits.
+///
+/// Both lowercase and uppercase hex digits are accepted.
+impl FromStr for TreeDigest {
+    type Err = ParseDigestError;
+
+    fn from_str(text: &str) -> Result<TreeDigest, ParseDigestError> {
+        text.parse()
+            .map(|digest: Digest| TreeDigest(digest.to_bytes()))
+    }
+}
+
+/// Computes the leaf digest of a chunk.
+#[cfg(feature = "rayon")]
+fn leaf(chunk: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut hasher = TentHash::new();
+    hasher.update(chunk);
+    finalize_leaf(hasher)
+}
+
+/// Computes the leaf digest of a chunk that's been fed to `hasher`.
+fn finalize_leaf(mut hasher: TentHash) -> [u8; DIGEST_SIZE] {
+    hasher.update([LEAF_SUFFIX]);
+    hasher.finalize()
+}
+
+/// Computes the digest of a non-leaf node.
+fn node(left: &[u8; DIGEST_SIZE], right: &[u8; DIGEST_SIZE]) -> [u8; DIGEST_SIZE] {
+    let mut bytes = [0u8; DIGEST_SIZE * 2 + 1];
+    bytes[..DIGEST_SIZE].copy_from_slice(left);
+    bytes[DIGEST_SIZE..(DIGEST_SIZE * 2)].copy_from_slice(right);
+    bytes[DIGEST_SIZE * 2] = NODE_SUFFIX;
+    crate::hash(bytes)
+}
+
+/// Computes the final digest from the digest of the top of the tree.
+fn root(top: &[u8; DIGEST_SIZE]) -> TreeDigest {
+    let mut bytes = [0u8; DIGEST_SIZE + 1];
+    bytes[..DIGEST_SIZE].copy_from_slice(top);
+    bytes[DIGEST_SIZE] = ROOT_SUFFIX;
+    TreeDigest(crate::hash(bytes))
+}
+
+/// The length in bytes of the left subtree of a tree over `len` bytes of
+/// input, where `len > CHUNK_SIZE`.
+#[cfg(feature = "rayon")]
+fn left_subtree_len(len: usize) -> usize {
+    let chunks = len.div_ceil(CHUNK_SIZE);
+    split_point(chunks as u64) as usize * CHUNK_SIZE
+}
+
+/// The number of leaves in the left subtree of a tree with `n` leaves, which
+/// must be at least 2: the largest power of two less than `n`.
+///
+/// This is shared with the `merkle` module, whose trees have the same shape.
+pub(crate) fn split_point(n: u64) -> u64 {
+    debug_assert!(n >= 2);
+    1 << (63 - (n - 1).leading_zeros())
+}
+
+// The chunk size must be a multiple of the block size, so that chunk data is
+// hashed straight from the input without going through `TentHash`'s buffer.
+const _: () = assert!(CHUNK_SIZE % BLOCK_SIZE == 0);
@@ -0,0 +1,186 @@
+//! Checking data against expected digests.
+
+use core::fmt;
+
+use crate::{
+    encoding, multihash::Multihash, Digest, DigestExt, TentHash, DIGEST_SIZE, TRUNCATED_SIZE,
+};
+
+/// Checks that the TentHash digest of `data` is `expected`.
+///
+/// `expected` can be a full 160-bit digest (`[u8; 20]` or [`Digest`]), or a
+/// digest truncated to 128 bits (`[u8; 16]`, as returned by
+/// [`DigestExt::to_16_bytes()`]), in which case only the first 128 bits are
+/// compared.  It can also be a [`Multihash`] of either size.
+///
+/// Note that the comparison is *not* constant time.  This is fine because
+/// TentHash isn't intended for use where security matters anyway.
+///
+/// # Example
+///
+/// ```rust
+/// use tenthash::DigestExt;
+///
+/// let expected = tenthash::hash("Hello world!");
+///
+/// assert!(tenthash::verify("Hello world!", &expected).is_ok());
+/// assert!(tenthash::verify("Hello world!", &expected.to_16_bytes()).is_ok());
+///
+/// let err = tenthash::verify("Goodbye world!", &expected).unwrap_err();
+/// assert_eq!(err.expected(), &expected[..]);
+/// ```
+pub fn verify<E: ExpectedDigest + ?Sized>(
+    data: impl AsRef<[u8]>,
+    expected: &E,
+) -> Result<(), Mismatch> {
+    Mismatch::check(crate::hash(data), expected.expected_bytes())
+}
+
+impl TentHash {
+    /// Finalizes the hash and checks that the digest is `expected`.
+    ///
+    /// This is the incremental equivalent of [`verify()`], and accepts the
+    /// same kinds of expected digests, either by value or by reference.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tenthash::TentHash;
+    /// let expected = tenthash::hash("Hello world!");
+    ///
+    /// let mut hasher = TentHash::new();
+    /// hasher.update("Hello");
+    /// hasher.update(" world!");
+    ///
+    /// assert!(hasher.finalize_verify(expected).is_ok());
+    /// ```
+    pub fn finalize_verify(self, expected: impl ExpectedDigest) -> Result<(), Mismatch> {
+        Mismatch::check(self.finalize(), expected.expected_bytes())
+    }
+}
+
+/// A digest that data can be checked against with [`verify()`] and
+/// [`TentHash::finalize_verify()`].
+///
+/// This is implemented for full 160-bit digests (`[u8; 20]` and [`Digest`]),
+/// 128-bit truncated digests (`[u8; 16]`), [`Multihash`], and references to
+/// all of them.  It can't be implemented outside of this crate.
+pub trait ExpectedDigest: sealed::Sealed {
+    /// Returns the expected digest bytes, either 20 or 16 of them.
+    fn expected_bytes(&self) -> &[u8];
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for [u8; DIGEST_SIZE] {}
+impl ExpectedDigest for [u8; DIGEST_SIZE] {
+    #[inline(always)]
+    fn expected_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl sealed::Sealed for [u8; TRUNCATED_SIZE] {}
+impl ExpectedDigest for [u8; TRUNCATED_SIZE] {
+    #[inline(always)]
+    fn expected_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl sealed::Sealed for Digest {}
+impl ExpectedDigest for Digest {
+    #[inline(always)]
+    fn expected_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl sealed::Sealed for Multihash {}
+impl ExpectedDigest for Multihash {
+    #[inline(always)]
+    fn expected_bytes(&self) -> &[u8] {
+        self.digest()
+    }
+}
+
+impl<T: ExpectedDigest + ?Sized> sealed::Sealed for &T {}
+impl<T: ExpectedDigest + ?Sized> ExpectedDigest for &T {
+    #[inline(always)]
+    fn expected_bytes(&self) -> &[u8] {
+        (**self).expected_bytes()
+    }
+}
+
+/// The error returned when a digest doesn't match the expected one.
+///
+/// Contains both digests.  When the expected digest was truncated to 128
+/// bits, so is the actual digest as reported by
+/// [`actual()`](Mismatch::actual) and `Display`.
+///
+/// # Example
+///
+/// ```rust
+/// let expected = tenthash::hash("Hello world!");
+/// let err = tenthash::verify("Goodbye world!", &expected).unwrap_err();
+///
+/// assert_eq!(err.actual(), &tenthash::hash("Goodbye world!")[..]);
+/// assert!(err.to_string().starts_with("TentHash digest mismatch: expected 155f0a35"));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    expected: [u8; DIGEST_SIZE],
+    actual: [u8; DIGEST_SIZE],
+    len: u8,
+}
+
+impl Mismatch {
+    fn check(actual: [u8; DIGEST_SIZE], expected: &[u8]) -> Result<(), Mismatch> {
+        let matches = match expected.len() {
+            DIGEST_SIZE => actual[..] == *expected,
+            TRUNCATED_SIZE => actual.to_16_bytes()[..] == *expected,
+            // `ExpectedDigest` is sealed, so this can't happen.
+            _ => unreachable!(),
+        };
+        if matches {
+            return Ok(());
+        }
+
+        let mut mismatch = Mismatch {
+            expected: [0; DIGEST_SIZE],
+            actual: [0; DIGEST_SIZE],
+            len: expected.len() as u8,
+        };
+        mismatch.expected[..expected.len()].copy_from_slice(expected);
+        mismatch.actual[..expected.len()].copy_from_slice(&actual[..expected.len()]);
+        Err(mismatch)
+    }
+
+    /// Returns the expected digest, either 20 or 16 bytes.
+    pub fn expected(&self) -> &[u8] {
+        &self.expected[..self.len as usize]
+    }
+
+    /// Returns the actual digest, with the same length as the expected one.
+    pub fn actual(&self) -> &[u8] {
+        &self.actual[..self.len as usize]
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut expected = [0u8; encoding::HEX_LEN];
+        let mut actual = [0u8; encoding::HEX_LEN];
+        write!(
+            f,
+            "TentHash digest mismatch: expected {}, got {}",
+            encoding::encode_hex_bytes(self.expected(), &mut expected),
+            encoding::encode_hex_bytes(self.actual(), &mut actual),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Mismatch {}
@@ -0,0 +1,102 @@
+use std::io::{IoSlice, Read, Write};
+
+use tenthash::{HashingReader, HashingWriter};
+
+mod common;
+use common::test_data;
+
+/// A writer that accepts at most `max_write` bytes per write.
+struct ChoppyWriter {
+    data: Vec<u8>,
+    max_write: usize,
+}
+
+impl Write for ChoppyWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.max_write.min(buf.len());
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn reader() {
+    for len in [0, 1, 31, 32, 33, 100_000] {
+        let data = test_data(len);
+        let mut reader = HashingReader::new(&data[..]);
+
+        let mut contents = Vec::new();
+        reader.read_to_end(&mut contents).unwrap();
+
+        assert_eq!(contents, data);
+        assert_eq!(reader.digest(), tenthash::hash(&data));
+    }
+}
+
+#[test]
+fn reader_partial() {
+    let data = test_data(1000);
+    let mut reader = HashingReader::new(&data[..]);
+
+    let mut buf = [0u8; 100];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.digest(), tenthash::hash(&data[..100]));
+    assert_eq!(reader.get_ref().len(), 900);
+}
+
+#[test]
+fn writer() {
+    let data = test_data(100_000);
+    let mut writer = HashingWriter::new(ChoppyWriter {
+        data: Vec::new(),
+        max_write: 333,
+    });
+
+    for chunk in data.chunks(1000) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.flush().unwrap();
+
+    assert_eq!(writer.digest(), tenthash::hash(&data));
+    assert_eq!(writer.into_inner().data, data);
+}
+
+#[test]
+fn writer_vectored() {
+    let mut writer = HashingWriter::new(ChoppyWriter {
+        data: Vec::new(),
+        max_write: 7,
+    });
+
+    // Only the bytes that the inner writer accepts are hashed.
+    let n = writer
+        .write_vectored(&[IoSlice::new(b"Hello"), IoSlice::new(b" world!")])
+        .unwrap();
+    assert_eq!(n, 5);
+    assert_eq!(writer.digest(), tenthash::hash("Hello"));
+
+    let mut writer = HashingWriter::new(Vec::new());
+    let n = writer
+        .write_vectored(&[IoSlice::new(b"Hello"), IoSlice::new(b" world!")])
+        .unwrap();
+    assert_eq!(n, 12);
+    assert_eq!(writer.digest(), tenthash::hash("Hello world!"));
+}
+
+/// Verifying data while copying it, in one pass.
+#[test]
+fn copy() {
+    let data = test_data(100_000);
+    let mut reader = HashingReader::new(&data[..]);
+    let mut writer = HashingWriter::new(Vec::new());
+
+    std::io::copy(&mut reader, &mut writer).unwrap();
+
+    assert_eq!(reader.digest(), tenthash::hash(&data));
+    assert_eq!(writer.digest(), tenthash::hash(&data));
+    assert_eq!(writer.into_inner(), data);
+}
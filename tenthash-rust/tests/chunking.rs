@@ -0,0 +1,212 @@
+use tenthash::chunking::{Chunk, Chunker};
+
+mod common;
+use common::to_hex;
+
+/// Pseudo-random test data, so that chunk boundaries aren't periodic.
+fn random_data(len: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9e3779b97f4a7c15) | 1;
+    (0..len)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+fn small_chunker() -> Chunker {
+    Chunker::new(256, 1024, 4096)
+}
+
+/// The first chunks of `random_data(20000, 1)` with `small_chunker()`.
+const TEST_VECTORS: &[(u64, usize, &str)] = &[
+    (0, 736, "a18b56802d2939547bc01a54f29a0089cfb2fe50"),
+    (736, 1379, "46ce56c97fc0e491e836ace905aeae7ea099ed1d"),
+    (2115, 1856, "7601d36333b0b4689ffe7478ed34d7c55cd8bf56"),
+    (3971, 1406, "db6f342640f251f15db7f37b9d93f72528544c77"),
+    (5377, 1344, "c4fd00a89191d2a45f8a771fa12eaddb933d3518"),
+    (6721, 1871, "c9994c1e10334955143ac4443ac072b71377b71a"),
+];
+
+/// Checks that `chunks` tile `data` exactly, respect the chunker's size
+/// limits, and have the right digests.
+fn check_chunks(chunker: &Chunker, data: &[u8], chunks: &[Chunk]) {
+    let mut offset = 0;
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.offset, offset as u64);
+        assert!(chunk.len <= chunker.max_size());
+        if i + 1 < chunks.len() {
+            assert!(chunk.len >= chunker.min_size());
+        }
+        assert_eq!(chunk.digest, tenthash::hash(&data[offset..][..chunk.len]));
+        offset += chunk.len;
+    }
+    assert_eq!(offset, data.len());
+}
+
+#[test]
+fn test_vectors() {
+    let data = random_data(20000, 1);
+    let chunks: Vec<_> = small_chunker()
+        .chunks(&data)
+        .take(TEST_VECTORS.len())
+        .map(|c| (c.offset, c.len, to_hex(c.digest)))
+        .collect();
+    let expected: Vec<_> = TEST_VECTORS
+        .iter()
+        .map(|&(offset, len, digest)| (offset, len, digest.to_string()))
+        .collect();
+    assert_eq!(chunks, expected);
+}
+
+#[test]
+fn chunks_tile_data() {
+    let chunker = small_chunker();
+    for len in [0, 1, 255, 256, 257, 1024, 4095, 4096, 4097, 100_000] {
+        let data = random_data(len, 3);
+        let chunks: Vec<_> = chunker.chunks(&data).collect();
+        check_chunks(&chunker, &data, &chunks);
+    }
+
+    // Data without any boundaries gets max-size chunks.
+    let data = vec![0u8; 10_000];
+    let lens: Vec<_> = chunker.chunks(&data).map(|c| c.len).collect();
+    assert_eq!(lens, [4096, 4096, 1808]);
+}
+
+#[test]
+fn empty() {
+    assert_eq!(Chunker::default().chunks(&[]).next(), None);
+}
+
+#[test]
+fn average_size() {
+    let chunker = Chunker::default();
+    let data = random_data(16 << 20, 4);
+    let chunks: Vec<_> = chunker.chunks(&data).collect();
+    check_chunks(&chunker, &data, &chunks);
+
+    let avg = data.len() / chunks.len();
+    assert!(avg > chunker.avg_size() / 2 && avg < chunker.avg_size() * 2);
+}
+
+#[test]
+fn insertion_only_affects_nearby_chunks() {
+    let chunker = small_chunker();
+    let data = random_data(200_000, 5);
+    let mut edited = data.clone();
+    edited.insert(50_000, 0xff);
+
+    let digests: Vec<_> = chunker.chunks(&data).map(|c| c.digest).collect();
+    let edited_digests: Vec<_> = chunker.chunks(&edited).map(|c| c.digest).collect();
+
+    let changed = edited_digests
+        .iter()
+        .filter(|digest| !digests.contains(digest))
+        .count();
+    assert!(changed <= 2, "{} chunks changed", changed);
+}
+
+#[test]
+fn next_boundary() {
+    let chunker = small_chunker();
+    let data = random_data(20000, 1);
+    assert_eq!(chunker.next_boundary(&data), TEST_VECTORS[0].1);
+    assert_eq!(chunker.next_boundary(&data[..100]), 100);
+    assert_eq!(chunker.next_boundary(&[]), 0);
+}
+
+/// The chunker switches from the stricter to the looser mask after chunk
+/// lengths of `avg_size()`.
+#[test]
+fn mask_switch_at_average_size() {
+    let chunker = small_chunker();
+
+    // Found with the stricter mask at the last byte of an `avg_size()` chunk.
+    assert_eq!(chunker.next_boundary(&random_data(4096, 4333)), 1024);
+
+    // Only the looser mask matches at that byte here, which is too early for
+    // it to apply.
+    assert_eq!(chunker.next_boundary(&random_data(4096, 796)), 1936);
+}
+
+#[test]
+#[should_panic]
+fn non_power_of_two_average() {
+    Chunker::new(256, 1000, 4096);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+#[should_panic]
+fn average_too_large() {
+    Chunker::new(1, 1 << 63, 1 << 63);
+}
+
+#[test]
+#[should_panic]
+fn min_larger_than_average() {
+    Chunker::new(2048, 1024, 4096);
+}
+
+#[test]
+#[should_panic]
+fn max_smaller_than_average() {
+    Chunker::new(256, 1024, 1000);
+}
+
+#[cfg(feature = "std")]
+mod reader {
+    use super::*;
+    use std::io::{self, Read};
+
+    /// A reader that returns at most `max_read` bytes per read.
+    struct TrickleReader<'a> {
+        data: &'a [u8],
+        max_read: usize,
+    }
+
+    impl Read for TrickleReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.max_read).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn matches_slice() {
+        let chunker = small_chunker();
+        let data = random_data(100_000, 6);
+        let expected: Vec<_> = chunker.chunks(&data).collect();
+
+        for max_read in [1, 7, 1000, 4096, 5000, 1 << 20] {
+            let reader = TrickleReader {
+                data: &data,
+                max_read,
+            };
+            let chunks: Vec<_> = chunker
+                .chunks_reader(reader)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(chunks, expected, "max read {}", max_read);
+        }
+    }
+
+    #[test]
+    fn read_error() {
+        struct FailingReader;
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("oops"))
+            }
+        }
+
+        let mut chunks = Chunker::default().chunks_reader(FailingReader);
+        assert!(chunks.next().unwrap().is_err());
+    }
+}
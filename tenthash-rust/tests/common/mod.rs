@@ -0,0 +1,38 @@
+//! Fixtures shared by the integration tests.
+
+// Each test crate only uses some of these.
+#![allow(dead_code)]
+
+/// A short test message, and its digest as hex.
+pub const FOX: &str = "The quick brown fox jumps over the lazy dog.";
+pub const FOX_HEX: &str = "de77f1c134228be1b5b25c941d5102f87f3e6d39";
+
+/// The digest of `FOX`.
+pub fn fox() -> [u8; 20] {
+    tenthash::hash(FOX)
+}
+
+/// Deterministic test data of the given length.
+///
+/// This is the same data as the `pattern:` messages in the shared test
+/// vector corpus.
+pub fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// Like `test_data()`, but different for each `seed`, for tests that hash
+/// several messages of the same length at once.
+pub fn seeded_test_data(len: usize, seed: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| ((i * 7 + seed * 13) % 251) as u8)
+        .collect()
+}
+
+/// Returns the bytes as a lowercase hex string.
+pub fn to_hex(bytes: impl AsRef<[u8]>) -> String {
+    bytes
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
@@ -0,0 +1,52 @@
+use tenthash::const_hash;
+
+mod common;
+use common::test_data;
+
+const TEST_VECTORS: &[&[u8]] = &[
+    &[],
+    &[0],
+    b"0123456789",
+    b"abcdefghijklmnopqrstuvwxyz",
+    b"This string is exactly 32 bytes.",
+    b"The quick brown fox jumps over the lazy dog.",
+    b"Lorem ipsum dolor sit amet, consectetur adipisicing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.",
+];
+
+/// The test vectors, hashed at compile time.
+const CONST_DIGESTS: [[u8; 20]; 7] = [
+    const_hash(TEST_VECTORS[0]),
+    const_hash(TEST_VECTORS[1]),
+    const_hash(TEST_VECTORS[2]),
+    const_hash(TEST_VECTORS[3]),
+    const_hash(TEST_VECTORS[4]),
+    const_hash(TEST_VECTORS[5]),
+    const_hash(TEST_VECTORS[6]),
+];
+
+#[test]
+fn test_vectors_at_compile_time() {
+    for (data, digest) in TEST_VECTORS.iter().zip(CONST_DIGESTS) {
+        assert_eq!(digest, tenthash::hash(data));
+    }
+}
+
+#[test]
+fn known_digest_at_compile_time() {
+    const FOX: [u8; 20] = const_hash(b"The quick brown fox jumps over the lazy dog.");
+    assert_eq!(
+        FOX,
+        [
+            0xde, 0x77, 0xf1, 0xc1, 0x34, 0x22, 0x8b, 0xe1, 0xb5, 0xb2, 0x5c, 0x94, 0x1d, 0x51,
+            0x02, 0xf8, 0x7f, 0x3e, 0x6d, 0x39,
+        ]
+    );
+}
+
+#[test]
+fn matches_hash_at_run_time() {
+    for len in 0..=200 {
+        let data = test_data(len);
+        assert_eq!(const_hash(&data), tenthash::hash(&data), "length {}", len);
+    }
+}
@@ -0,0 +1,76 @@
+//! Differential tests against the reference implementation in
+//! `reference/rust`, with randomly generated inputs.
+//!
+//! The fuzz targets in `fuzz/` check the same things, but need nightly Rust
+//! and `cargo fuzz`.  These run as part of the normal test suite, except that
+//! the tests of the SIMD code paths need the `std` feature.
+
+use proptest::{collection::vec, prelude::*, sample::Index};
+use tenthash::TentHash;
+
+/// Arbitrary data, with lengths covering several blocks and the internal
+/// buffer of `TentHash`.
+fn data() -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..2048)
+}
+
+proptest! {
+    #[test]
+    fn hash(data in data()) {
+        prop_assert_eq!(tenthash::hash(&data), tenthash_reference::hash(&data));
+    }
+
+    #[test]
+    fn const_hash(data in data()) {
+        prop_assert_eq!(tenthash::const_hash(&data), tenthash_reference::hash(&data));
+    }
+
+    #[test]
+    fn update_splits(data in data(), splits in vec(any::<Index>(), 0..16)) {
+        let mut splits: Vec<usize> = splits.iter().map(|i| i.index(data.len() + 1)).collect();
+        splits.sort_unstable();
+
+        let mut hasher = TentHash::new();
+        let mut start = 0;
+        for &split in &splits {
+            hasher.update(&data[start..split]);
+            start = split;
+        }
+        hasher.update(&data[start..]);
+
+        prop_assert_eq!(hasher.finalize(), tenthash_reference::hash(&data));
+    }
+
+    #[test]
+    fn update_bytes(data in vec(any::<u8>(), 0..256)) {
+        let mut hasher = TentHash::new();
+        for byte in &data {
+            hasher.update([*byte]);
+        }
+        prop_assert_eq!(hasher.finalize(), tenthash_reference::hash(&data));
+    }
+}
+
+// The multi-message functions only use SIMD with the `std` feature, and
+// are otherwise just loops over `hash()`.
+#[cfg(feature = "std")]
+proptest! {
+    #[test]
+    fn hash_many(messages in vec(data(), 0..40)) {
+        let expected: Vec<[u8; 20]> = messages.iter().map(|m| tenthash_reference::hash(m)).collect();
+
+        prop_assert_eq!(tenthash::hash_many(&messages), expected.clone());
+
+        let mut digests = vec![[0u8; 20]; messages.len()];
+        tenthash::hash_many_into(&messages, &mut digests);
+        prop_assert_eq!(digests, expected);
+    }
+
+    #[test]
+    fn hash_4x(messages in [data(), data(), data(), data()]) {
+        let digests = tenthash::hash_4x(messages.clone());
+        for (message, digest) in messages.iter().zip(digests) {
+            prop_assert_eq!(digest, tenthash_reference::hash(message));
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use digest::{Digest, DynDigest, FixedOutputReset};
+use tenthash::TentHash;
+
+const MESSAGE: &[u8] = b"The quick brown fox jumps over the lazy dog.";
+
+#[test]
+fn digest_one_shot() {
+    let digest = <TentHash as Digest>::digest(MESSAGE);
+    assert_eq!(digest.as_slice(), &tenthash::hash(MESSAGE));
+    assert_eq!(<TentHash as Digest>::output_size(), 20);
+}
+
+#[test]
+fn digest_streaming() {
+    let mut hasher = <TentHash as Digest>::new();
+    Digest::update(&mut hasher, &MESSAGE[..10]);
+    let hasher = hasher.chain_update(&MESSAGE[10..]);
+    assert_eq!(
+        Digest::finalize(hasher).as_slice(),
+        &tenthash::hash(MESSAGE)
+    );
+}
+
+#[test]
+fn digest_reset() {
+    let mut hasher = TentHash::new();
+    hasher.update(b"Some data that should be discarded.");
+    Digest::reset(&mut hasher);
+    hasher.update(MESSAGE);
+    let digest = hasher.finalize_fixed_reset();
+    assert_eq!(digest.as_slice(), &tenthash::hash(MESSAGE));
+
+    // After `finalize_fixed_reset()` the hasher should be back to its initial
+    // state.
+    assert_eq!(Digest::finalize(hasher).as_slice(), &tenthash::hash([]));
+}
+
+#[test]
+fn dyn_digest() {
+    let mut hasher: Box<dyn DynDigest> = Box::new(TentHash::new());
+    DynDigest::update(&mut *hasher, MESSAGE);
+    assert_eq!(hasher.output_size(), 20);
+    assert_eq!(&*hasher.finalize(), &tenthash::hash(MESSAGE));
+}
+
+/// Ensures that `TentHash` is usable in code that's generic over hashes.
+#[test]
+fn generic_usage() {
+    fn generic_hash<D: Digest>(data: &[u8]) -> Vec<u8> {
+        D::digest(data).to_vec()
+    }
+
+    assert_eq!(generic_hash::<TentHash>(MESSAGE), tenthash::hash(MESSAGE));
+}
@@ -0,0 +1,78 @@
+use std::collections::{BTreeSet, HashSet};
+
+use tenthash::{Digest, DigestExt, ParseDigestError};
+
+mod common;
+use common::{fox, FOX_HEX};
+
+#[test]
+fn format() {
+    let digest = Digest::from(fox());
+
+    assert_eq!(digest.to_string(), FOX_HEX);
+    assert_eq!(format!("{:x}", digest), FOX_HEX);
+    assert_eq!(format!("{:X}", digest), FOX_HEX.to_uppercase());
+    assert_eq!(format!("{:?}", digest), format!("Digest({})", FOX_HEX));
+    assert_eq!(format!("{:>42}", digest), format!("  {}", FOX_HEX));
+}
+
+#[test]
+fn parse() {
+    assert_eq!(FOX_HEX.parse::<Digest>(), Ok(Digest::from(fox())));
+    assert_eq!(
+        FOX_HEX.to_uppercase().parse::<Digest>(),
+        Ok(Digest::from(fox()))
+    );
+
+    assert_eq!(
+        "".parse::<Digest>(),
+        Err(ParseDigestError::InvalidLength(0))
+    );
+    assert_eq!(
+        FOX_HEX[..38].parse::<Digest>(),
+        Err(ParseDigestError::InvalidLength(38))
+    );
+    assert_eq!(
+        format!("{}00", FOX_HEX).parse::<Digest>(),
+        Err(ParseDigestError::InvalidLength(42))
+    );
+    assert_eq!(
+        format!("{}g{}", &FOX_HEX[..5], &FOX_HEX[6..]).parse::<Digest>(),
+        Err(ParseDigestError::InvalidDigit(5))
+    );
+    assert_eq!(
+        format!("+{}", &FOX_HEX[1..]).parse::<Digest>(),
+        Err(ParseDigestError::InvalidDigit(0))
+    );
+}
+
+#[test]
+fn conversions() {
+    let bytes = tenthash::hash("The quick brown fox jumps over the lazy dog.");
+    let digest = Digest::from(bytes);
+
+    assert_eq!(digest, bytes);
+    assert_eq!(bytes, digest);
+    assert_eq!(digest.as_bytes(), &bytes);
+    assert_eq!(digest.as_ref(), &bytes[..]);
+    assert_eq!(<[u8; 20]>::from(digest), bytes);
+    assert_eq!(Digest::from_bytes(bytes).to_bytes(), bytes);
+
+    assert_eq!(digest.to_16_bytes(), bytes.to_16_bytes());
+    assert_eq!(digest.to_u128(), bytes.to_u128());
+    assert_eq!(DigestExt::to_u128(digest), bytes.to_u128());
+}
+
+#[test]
+fn ordering_and_hashing() {
+    let a = Digest::from(tenthash::hash("a"));
+    let b = Digest::from(tenthash::hash("b"));
+
+    assert_ne!(a, b);
+    assert_eq!(a.cmp(&b), a.as_bytes().cmp(b.as_bytes()));
+
+    let hash_set: HashSet<Digest> = [a, b, a].into_iter().collect();
+    let btree_set: BTreeSet<Digest> = [a, b, a].into_iter().collect();
+    assert_eq!(hash_set.len(), 2);
+    assert_eq!(btree_set.len(), 2);
+}
@@ -0,0 +1,267 @@
+use std::path::{Path, PathBuf};
+
+use tenthash::{
+    directory::{fingerprint, EntryKind, Options, SymlinkPolicy},
+    Digest, TentHash,
+};
+
+/// A temporary directory that's deleted when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!(
+            "tenthash_directory_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    fn write(&self, rel_path: &str, contents: &str) {
+        let path = self.0.join(rel_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn mkdir(&self, rel_path: &str) {
+        std::fs::create_dir_all(self.0.join(rel_path)).unwrap();
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Builds the same small tree, with entries created in the given order.
+fn build_tree(dir: &TempDir, reversed: bool) {
+    let mut files = vec![
+        ("b.txt", "bee"),
+        ("a.txt", "ay"),
+        ("sub/c.txt", "sea"),
+        ("sub/deeper/d.txt", ""),
+    ];
+    if reversed {
+        files.reverse();
+    }
+    for (path, contents) in files {
+        dir.write(path, contents);
+    }
+    dir.mkdir("empty");
+}
+
+/// Computes a directory node digest by hand, following the documented
+/// canonical encoding.
+fn directory_node(entries: &[(u8, &str, Digest)]) -> Digest {
+    let mut hasher = TentHash::new();
+    hasher.update(b"tenthash-dir-v1");
+    hasher.update([0]);
+    for (kind, name, digest) in entries {
+        hasher.update([*kind]);
+        hasher.update((name.len() as u64).to_le_bytes());
+        hasher.update(name);
+        hasher.update(digest);
+    }
+    hasher.finalize().into()
+}
+
+fn file_node(contents: &str) -> Digest {
+    tenthash::hash(contents).into()
+}
+
+#[test]
+fn canonical_encoding() {
+    let dir = TempDir::new("canonical_encoding");
+    build_tree(&dir, false);
+
+    let deeper = directory_node(&[(b'f', "d.txt", file_node(""))]);
+    let sub = directory_node(&[(b'f', "c.txt", file_node("sea")), (b'd', "deeper", deeper)]);
+    let empty = directory_node(&[]);
+    let root = directory_node(&[
+        (b'f', "a.txt", file_node("ay")),
+        (b'f', "b.txt", file_node("bee")),
+        (b'd', "empty", empty),
+        (b'd', "sub", sub),
+    ]);
+
+    let fp = fingerprint(&dir.0, &Options::default()).unwrap();
+    assert_eq!(fp.root, root);
+
+    let manifest: Vec<(&str, EntryKind, Digest)> = fp
+        .entries
+        .iter()
+        .map(|e| (e.path.as_str(), e.kind, e.digest))
+        .collect();
+    assert_eq!(
+        manifest,
+        [
+            ("a.txt", EntryKind::File, file_node("ay")),
+            ("b.txt", EntryKind::File, file_node("bee")),
+            ("empty", EntryKind::Directory, empty),
+            ("sub", EntryKind::Directory, sub),
+            ("sub/c.txt", EntryKind::File, file_node("sea")),
+            ("sub/deeper", EntryKind::Directory, deeper),
+            ("sub/deeper/d.txt", EntryKind::File, file_node("")),
+        ]
+    );
+    assert!(fp.entries.iter().all(|e| e.permissions.is_none()));
+}
+
+/// Guards against accidental changes to the canonical encoding.
+#[test]
+fn stable_root_digest() {
+    let dir = TempDir::new("stable_root_digest");
+    build_tree(&dir, false);
+
+    let fp = fingerprint(&dir.0, &Options::default()).unwrap();
+    assert_eq!(
+        fp.root.to_string(),
+        "b86a9f6c797bc2d60d36a27ad3834b415444ed64"
+    );
+
+    let dir = TempDir::new("stable_root_digest_empty");
+    let fp = fingerprint(&dir.0, &Options::default()).unwrap();
+    assert_eq!(
+        fp.root.to_string(),
+        "a2cad1707aa467b78679facd25efd38309456634"
+    );
+}
+
+#[test]
+fn deterministic() {
+    let dir1 = TempDir::new("deterministic_1");
+    let dir2 = TempDir::new("deterministic_2");
+    build_tree(&dir1, false);
+    build_tree(&dir2, true);
+
+    let fp1 = fingerprint(&dir1.0, &Options::default()).unwrap();
+    let fp2 = fingerprint(&dir2.0, &Options::default()).unwrap();
+    assert_eq!(fp1, fp2);
+}
+
+#[test]
+fn detects_changes() {
+    let dir = TempDir::new("detects_changes");
+    build_tree(&dir, false);
+    let original = fingerprint(&dir.0, &Options::default()).unwrap().root;
+
+    // Content change.
+    dir.write("sub/c.txt", "see");
+    let changed = fingerprint(&dir.0, &Options::default()).unwrap().root;
+    assert_ne!(original, changed);
+    dir.write("sub/c.txt", "sea");
+    assert_eq!(
+        fingerprint(&dir.0, &Options::default()).unwrap().root,
+        original
+    );
+
+    // Rename.
+    std::fs::rename(dir.0.join("a.txt"), dir.0.join("a2.txt")).unwrap();
+    assert_ne!(
+        fingerprint(&dir.0, &Options::default()).unwrap().root,
+        original
+    );
+    std::fs::rename(dir.0.join("a2.txt"), dir.0.join("a.txt")).unwrap();
+
+    // New empty directory.
+    dir.mkdir("empty2");
+    assert_ne!(
+        fingerprint(&dir.0, &Options::default()).unwrap().root,
+        original
+    );
+}
+
+#[test]
+fn single_file() {
+    let dir = TempDir::new("single_file");
+    dir.write("file", "contents");
+
+    let fp = fingerprint(dir.0.join("file"), &Options::default()).unwrap();
+    assert_eq!(fp.root, file_node("contents"));
+    assert!(fp.entries.is_empty());
+}
+
+#[test]
+fn missing_root() {
+    let err = fingerprint("this/does/not/exist", &Options::default()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[cfg(unix)]
+#[test]
+fn permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new("permissions");
+    build_tree(&dir, false);
+    let set_mode = |path: &Path, mode| {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap()
+    };
+    set_mode(&dir.0.join("a.txt"), 0o644);
+
+    let options = Options {
+        include_permissions: true,
+        ..Options::default()
+    };
+    let without = fingerprint(&dir.0, &Options::default()).unwrap().root;
+    let with = fingerprint(&dir.0, &options).unwrap();
+    assert_ne!(without, with.root);
+    assert_eq!(with.entries[0].permissions, Some(0o644));
+
+    // Permission changes only matter when permissions are included.
+    set_mode(&dir.0.join("a.txt"), 0o755);
+    assert_eq!(
+        fingerprint(&dir.0, &Options::default()).unwrap().root,
+        without
+    );
+    assert_ne!(fingerprint(&dir.0, &options).unwrap().root, with.root);
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks() {
+    use std::os::unix::fs::symlink;
+
+    let dir = TempDir::new("symlinks");
+    build_tree(&dir, false);
+    let plain = fingerprint(&dir.0, &Options::default()).unwrap();
+    symlink("sub/c.txt", dir.0.join("link")).unwrap();
+
+    let with_policy = |symlinks| {
+        fingerprint(
+            &dir.0,
+            &Options {
+                symlinks,
+                ..Options::default()
+            },
+        )
+    };
+
+    // Record.
+    let recorded = with_policy(SymlinkPolicy::Record).unwrap();
+    let link = recorded.entries.iter().find(|e| e.path == "link").unwrap();
+    assert_eq!(link.kind, EntryKind::Symlink);
+    assert_eq!(link.digest, file_node("sub/c.txt"));
+
+    // Follow.
+    let followed = with_policy(SymlinkPolicy::Follow).unwrap();
+    let link = followed.entries.iter().find(|e| e.path == "link").unwrap();
+    assert_eq!(link.kind, EntryKind::File);
+    assert_eq!(link.digest, file_node("sea"));
+    assert_ne!(followed.root, recorded.root);
+
+    // Skip.
+    assert_eq!(with_policy(SymlinkPolicy::Skip).unwrap(), plain);
+
+    // Error.
+    assert!(with_policy(SymlinkPolicy::Error).is_err());
+
+    // Cycles are an error when following.
+    symlink("..", dir.0.join("sub/parent")).unwrap();
+    assert!(with_policy(SymlinkPolicy::Follow).is_err());
+    assert!(with_policy(SymlinkPolicy::Record).is_ok());
+}
@@ -0,0 +1,141 @@
+use tenthash::encoding::{self, DecodeError, BASE32_LEN, BASE64_LEN, HEX_LEN};
+
+mod common;
+use common::{FOX, FOX_HEX};
+
+const FOX_BASE32: &str = "3Z37DQJUEKF6DNNSLSKB2UIC7B7T43JZ";
+const FOX_BASE64: &str = "3nfxwTQii-G1slyUHVEC-H8-bTk";
+
+const EMPTY_BASE32: &str = "NDECCO32O24O2JT53WZ5Q4L3WO3OPTAK";
+const EMPTY_BASE64: &str = "aMghO3p2uO0mfd2z2HF7s7bnzAo";
+
+fn test_digests() -> Vec<[u8; 20]> {
+    let mut digests: Vec<[u8; 20]> = (0..100).map(|i| tenthash::hash([i as u8])).collect();
+    digests.push([0x00; 20]);
+    digests.push([0xff; 20]);
+    digests
+}
+
+#[test]
+fn encode() {
+    let fox = tenthash::hash(FOX);
+    assert_eq!(encoding::encode_hex(&fox, &mut [0; HEX_LEN]), FOX_HEX);
+    assert_eq!(
+        encoding::encode_hex_upper(&fox, &mut [0; HEX_LEN]),
+        FOX_HEX.to_uppercase()
+    );
+    assert_eq!(
+        encoding::encode_base32(&fox, &mut [0; BASE32_LEN]),
+        FOX_BASE32
+    );
+    assert_eq!(
+        encoding::encode_base64url(&fox, &mut [0; BASE64_LEN]),
+        FOX_BASE64
+    );
+
+    let empty = tenthash::hash([]);
+    assert_eq!(
+        encoding::encode_base32(&empty, &mut [0; BASE32_LEN]),
+        EMPTY_BASE32
+    );
+    assert_eq!(
+        encoding::encode_base64url(&empty, &mut [0; BASE64_LEN]),
+        EMPTY_BASE64
+    );
+    assert_eq!(
+        encoding::encode_base64url(&[0xff; 20], &mut [0; BASE64_LEN]),
+        "__________________________8"
+    );
+}
+
+#[test]
+fn write() {
+    let fox = tenthash::hash(FOX);
+    let mut text = String::new();
+    encoding::write_hex(&fox, &mut text).unwrap();
+    encoding::write_hex_upper(&fox, &mut text).unwrap();
+    encoding::write_base32(&fox, &mut text).unwrap();
+    encoding::write_base64url(&fox, &mut text).unwrap();
+
+    assert_eq!(
+        text,
+        [FOX_HEX, &FOX_HEX.to_uppercase(), FOX_BASE32, FOX_BASE64].concat()
+    );
+}
+
+#[test]
+fn round_trip() {
+    for digest in test_digests() {
+        let hex = encoding::encode_hex(&digest, &mut [0; HEX_LEN]).to_string();
+        let hex_upper = encoding::encode_hex_upper(&digest, &mut [0; HEX_LEN]).to_string();
+        let base32 = encoding::encode_base32(&digest, &mut [0; BASE32_LEN]).to_string();
+        let base64 = encoding::encode_base64url(&digest, &mut [0; BASE64_LEN]).to_string();
+
+        assert_eq!(encoding::decode_hex(&hex), Ok(digest));
+        assert_eq!(encoding::decode_hex(&hex_upper), Ok(digest));
+        assert_eq!(encoding::decode_base32(&base32), Ok(digest));
+        assert_eq!(encoding::decode_base32(base32.to_lowercase()), Ok(digest));
+        assert_eq!(encoding::decode_base64url(&base64), Ok(digest));
+    }
+}
+
+#[test]
+fn decode_invalid_length() {
+    assert_eq!(encoding::decode_hex(""), Err(DecodeError::InvalidLength(0)));
+    assert_eq!(
+        encoding::decode_hex(&FOX_HEX[..39]),
+        Err(DecodeError::InvalidLength(39))
+    );
+    assert_eq!(
+        encoding::decode_base32(format!("{}A", FOX_BASE32)),
+        Err(DecodeError::InvalidLength(33))
+    );
+    // Padding isn't accepted.
+    assert_eq!(
+        encoding::decode_base64url(format!("{}=", FOX_BASE64)),
+        Err(DecodeError::InvalidLength(28))
+    );
+}
+
+#[test]
+fn decode_invalid_character() {
+    assert_eq!(
+        encoding::decode_hex(format!("{}g", &FOX_HEX[..39])),
+        Err(DecodeError::InvalidCharacter(39))
+    );
+    // Base32 doesn't use 0, 1, 8, or 9.
+    for c in ["0", "1", "8", "9", "="] {
+        assert_eq!(
+            encoding::decode_base32(format!("{}{}", c, &FOX_BASE32[1..])),
+            Err(DecodeError::InvalidCharacter(0))
+        );
+    }
+    // The standard base64 alphabet isn't accepted.
+    for c in ["+", "/", "="] {
+        assert_eq!(
+            encoding::decode_base64url(format!("{}{}", &FOX_BASE64[..10], c) + &FOX_BASE64[11..]),
+            Err(DecodeError::InvalidCharacter(10))
+        );
+    }
+}
+
+#[test]
+fn decode_non_canonical() {
+    // "9" has non-zero bits in the two unused low bits.
+    assert_eq!(
+        encoding::decode_base64url("__________________________9"),
+        Err(DecodeError::NonZeroTrailingBits)
+    );
+    assert_eq!(
+        encoding::decode_base64url("__________________________8"),
+        Ok([0xff; 20])
+    );
+}
+
+#[test]
+fn error_display() {
+    assert_eq!(
+        DecodeError::InvalidLength(3).to_string(),
+        "invalid encoded digest length: 3"
+    );
+}
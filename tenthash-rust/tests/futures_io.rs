@@ -0,0 +1,46 @@
+use futures::{executor::block_on, AsyncReadExt, AsyncWriteExt};
+
+use tenthash::{HashingReader, HashingWriter};
+
+mod common;
+use common::test_data;
+
+#[test]
+fn reader() {
+    let data = test_data(100_000);
+    let mut reader = HashingReader::new(&data[..]);
+
+    let mut contents = Vec::new();
+    block_on(reader.read_to_end(&mut contents)).unwrap();
+
+    assert_eq!(contents, data);
+    assert_eq!(reader.digest(), tenthash::hash(&data));
+}
+
+#[test]
+fn writer() {
+    let data = test_data(100_000);
+    let mut writer = HashingWriter::new(Vec::new());
+
+    block_on(async {
+        for chunk in data.chunks(1000) {
+            writer.write_all(chunk).await.unwrap();
+        }
+        writer.close().await.unwrap();
+    });
+
+    assert_eq!(writer.digest(), tenthash::hash(&data));
+    assert_eq!(writer.into_inner(), data);
+}
+
+#[test]
+fn copy() {
+    let data = test_data(100_000);
+    let mut reader = HashingReader::new(&data[..]);
+    let mut writer = HashingWriter::new(Vec::new());
+
+    block_on(futures::io::copy(&mut reader, &mut writer)).unwrap();
+
+    assert_eq!(reader.digest(), tenthash::hash(&data));
+    assert_eq!(writer.digest(), tenthash::hash(&data));
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+use tenthash::{BuildTentHasher, HasherExt, TentHash, TentHasher};
+
+#[derive(Hash)]
+struct Record {
+    id: u64,
+    size: usize,
+    offset: i16,
+    tag: u8,
+}
+
+#[test]
+fn write_is_update() {
+    let mut hasher = TentHasher::new();
+    hasher.write(b"Hello");
+    hasher.write(b" world!");
+
+    let digest = tenthash::hash("Hello world!");
+    assert_eq!(hasher.finish_digest(), digest);
+    assert_eq!(
+        hasher.finish(),
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    );
+}
+
+#[test]
+fn finish_does_not_reset() {
+    let mut hasher = TentHasher::new();
+    hasher.write(b"Hello");
+    let first = hasher.finish();
+    assert_eq!(hasher.finish(), first);
+
+    hasher.write(b" world!");
+    assert_eq!(hasher.finish_digest(), tenthash::hash("Hello world!"));
+}
+
+#[test]
+fn integers_are_portable() {
+    let mut hasher = TentHasher::new();
+    Record {
+        id: 0x0102030405060708,
+        size: 42,
+        offset: -2,
+        tag: 7,
+    }
+    .hash(&mut hasher);
+
+    let mut expected = TentHash::new();
+    expected.update(0x0102030405060708u64.to_le_bytes());
+    expected.update(42u64.to_le_bytes()); // `usize` is always 64 bits.
+    expected.update((-2i16).to_le_bytes());
+    expected.update([7]);
+    assert_eq!(hasher.finish_digest(), expected.finalize());
+}
+
+#[test]
+fn seeded() {
+    let mut hasher = TentHasher::with_seed(5);
+    hasher.write(b"Hello world!");
+
+    let mut expected = TentHash::with_seed(5);
+    expected.update("Hello world!");
+    assert_eq!(hasher.finish_digest(), expected.finalize());
+
+    let mut hasher = TentHasher::with_seed(5);
+    1234u32.hash(&mut hasher);
+    assert_eq!(
+        BuildTentHasher::with_seed(5).hash_one(1234u32),
+        hasher.finish()
+    );
+    assert_ne!(
+        BuildTentHasher::with_seed(5).hash_one(1234u32),
+        BuildTentHasher::new().hash_one(1234u32)
+    );
+}
+
+#[test]
+fn hash_map() {
+    let mut map: HashMap<String, usize, BuildTentHasher> = HashMap::default();
+    for i in 0..1000 {
+        map.insert(i.to_string(), i);
+    }
+    for i in 0..1000 {
+        assert_eq!(map.get(&i.to_string()), Some(&i));
+    }
+
+    // Separately created `BuildTentHasher`s hash identically.
+    assert_eq!(
+        BuildTentHasher::new().hash_one("key"),
+        BuildTentHasher::default().hash_one("key")
+    );
+}
@@ -0,0 +1,95 @@
+use std::io::{Read, Write};
+
+use tenthash::TentHash;
+
+mod common;
+use common::test_data;
+
+/// A reader that returns at most `max_read` bytes per read, and that
+/// interrupts every other read.
+struct ChoppyReader<'a> {
+    data: &'a [u8],
+    max_read: usize,
+    interrupt: bool,
+}
+
+impl Read for ChoppyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+
+        let n = self.max_read.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn write_matches_hash() {
+    for len in [0, 1, 31, 32, 33, 1000, 100_000] {
+        let data = test_data(len);
+
+        let mut hasher = TentHash::new();
+        for chunk in data.chunks(7) {
+            hasher.write_all(chunk).unwrap();
+        }
+        hasher.flush().unwrap();
+
+        assert_eq!(hasher.finalize(), tenthash::hash(&data));
+    }
+}
+
+#[test]
+fn io_copy() {
+    let data = test_data(200_000);
+
+    let mut hasher = TentHash::new();
+    let byte_count = std::io::copy(&mut &data[..], &mut hasher).unwrap();
+
+    assert_eq!(byte_count, data.len() as u64);
+    assert_eq!(hasher.finalize(), tenthash::hash(&data));
+}
+
+#[test]
+fn update_reader() {
+    for len in [0, 1, 31, 32, 33, 1000, 65536, 200_000] {
+        let data = test_data(len);
+
+        let mut hasher = TentHash::new();
+        hasher.update(b"prefix");
+        let byte_count = hasher
+            .update_reader(ChoppyReader {
+                data: &data,
+                max_read: 1000,
+                interrupt: false,
+            })
+            .unwrap();
+
+        let mut expected = b"prefix".to_vec();
+        expected.extend_from_slice(&data);
+
+        assert_eq!(byte_count, len as u64);
+        assert_eq!(hasher.finalize(), tenthash::hash(&expected));
+    }
+}
+
+#[test]
+fn hash_file() {
+    let data = test_data(100_000);
+    let path = std::env::temp_dir().join(format!("tenthash_io_test_{}", std::process::id()));
+    std::fs::write(&path, &data).unwrap();
+
+    let digest = tenthash::hash_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(digest.unwrap(), tenthash::hash(&data));
+}
+
+#[test]
+fn hash_file_missing() {
+    let err = tenthash::hash_file("this/file/does/not/exist").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,162 @@
+use tenthash::merkle::{self, Proof, ProofError, MAX_ENCODED_PROOF_LEN};
+
+mod common;
+use common::to_hex;
+
+fn leaf_data(n: usize) -> Vec<Vec<u8>> {
+    (0..n).map(|i| format!("leaf {}", i).into_bytes()).collect()
+}
+
+fn leaves(n: usize) -> Vec<[u8; 20]> {
+    leaf_data(n).iter().map(merkle::leaf_digest).collect()
+}
+
+/// A straightforward implementation of the RFC 9162 tree hash, built on
+/// plain TentHash.
+fn reference_root(leaf_data: &[Vec<u8>]) -> [u8; 20] {
+    match leaf_data.len() {
+        0 => tenthash::hash([]),
+        1 => tenthash::hash([&[0x00][..], &leaf_data[0]].concat()),
+        n => {
+            let mut k = 1;
+            while k * 2 < n {
+                k *= 2;
+            }
+            let left = reference_root(&leaf_data[..k]);
+            let right = reference_root(&leaf_data[k..]);
+            tenthash::hash([&[0x01][..], &left, &right].concat())
+        }
+    }
+}
+
+/// Roots of `leaves(n)`.
+const TEST_VECTORS: &[(usize, &str)] = &[
+    (0, "68c8213b7a76b8ed267dddb3d8717bb3b6e7cc0a"),
+    (1, "6fcce9b589ef8bd189f7d8371b3e7ad5770396a7"),
+    (2, "bf6d4ff7826610fcb3c22ccf286994103928e711"),
+    (3, "6d54a2f60f72a04471e9209871020b5208e6a9fc"),
+    (7, "ca99ed4cfeaaa94a670fb0983ac717731f464b64"),
+    (100, "69a4f4efaeba89da50f91c65995092a96eb8220c"),
+];
+
+fn encode(proof: &Proof) -> Vec<u8> {
+    proof.encode(&mut [0; MAX_ENCODED_PROOF_LEN]).to_vec()
+}
+
+#[test]
+fn test_vectors() {
+    for &(n, root) in TEST_VECTORS {
+        assert_eq!(to_hex(merkle::root(&leaves(n))), root, "{} leaves", n);
+    }
+}
+
+#[test]
+fn matches_reference() {
+    for n in 0..=33 {
+        let data = leaf_data(n);
+        let expected = reference_root(&data);
+        assert_eq!(merkle::root(&leaves(n)), expected, "{} leaves", n);
+        assert_eq!(merkle::root_of_data(&data), expected, "{} leaves", n);
+    }
+}
+
+#[test]
+fn proofs() {
+    for n in 1..=33 {
+        let data = leaf_data(n);
+        let leaves = leaves(n);
+        let root = merkle::root(&leaves);
+
+        for index in 0..n {
+            let proof = Proof::generate(&leaves, index);
+            assert_eq!(proof.index(), index as u64);
+            assert_eq!(proof.leaf_count(), n as u64);
+            assert!(proof.siblings().len() <= 6);
+
+            assert_eq!(proof.root_for(&leaves[index]), root);
+            assert!(proof.verify(&leaves[index], &root));
+            assert!(proof.verify_data(&data[index], &root));
+
+            // The wrong leaf or root doesn't verify.
+            let other = (index + 1) % n;
+            if other != index {
+                assert!(!proof.verify(&leaves[other], &root));
+            }
+            assert!(!proof.verify_data(b"not a leaf", &root));
+            assert!(!proof.verify(&leaves[index], &merkle::root(&leaves[1..])));
+        }
+    }
+}
+
+#[test]
+fn leaves_and_nodes_are_distinct() {
+    // An inner node's children, passed off as leaf data, don't give the
+    // same digest as the inner node.
+    let leaves = leaves(2);
+    let node = merkle::node_digest(&leaves[0], &leaves[1]);
+    assert_eq!(merkle::root(&leaves), node);
+    assert_ne!(
+        merkle::leaf_digest([&leaves[0][..], &leaves[1][..]].concat()),
+        node
+    );
+    assert_ne!(merkle::leaf_digest("data"), tenthash::hash("data"));
+}
+
+#[test]
+fn encoding() {
+    let leaves = leaves(7);
+    let proof = Proof::generate(&leaves, 4);
+    let bytes = encode(&proof);
+
+    let mut expected = vec![1];
+    expected.extend_from_slice(&4u64.to_le_bytes());
+    expected.extend_from_slice(&7u64.to_le_bytes());
+    expected.push(proof.siblings().len() as u8);
+    for sibling in proof.siblings() {
+        expected.extend_from_slice(sibling);
+    }
+    assert_eq!(bytes, expected);
+    assert_eq!(Proof::decode(&bytes), Ok(proof));
+
+    // Single-leaf trees have empty proofs.
+    let proof = Proof::generate(&leaves[..1], 0);
+    assert_eq!(encode(&proof).len(), 18);
+    assert_eq!(Proof::decode(&encode(&proof)), Ok(proof));
+}
+
+#[test]
+fn decode_errors() {
+    let leaves = leaves(7);
+    let bytes = encode(&Proof::generate(&leaves, 4));
+
+    assert_eq!(Proof::decode(&[]), Err(ProofError::InvalidLength(0)));
+    assert_eq!(
+        Proof::decode(&bytes[..bytes.len() - 1]),
+        Err(ProofError::InvalidLength(bytes.len() - 1))
+    );
+    assert_eq!(
+        Proof::decode(&[&bytes[..], &[0]].concat()),
+        Err(ProofError::InvalidLength(bytes.len() + 1))
+    );
+
+    let mut bad = bytes.clone();
+    bad[0] = 2;
+    assert_eq!(Proof::decode(&bad), Err(ProofError::UnsupportedVersion(2)));
+
+    let mut bad = bytes.clone();
+    bad[1] = 7;
+    assert_eq!(Proof::decode(&bad), Err(ProofError::IndexOutOfBounds));
+
+    let mut bad = bytes.clone();
+    bad[17] += 1;
+    assert_eq!(
+        Proof::decode(&bad),
+        Err(ProofError::WrongSiblingCount(bad[17]))
+    );
+}
+
+#[test]
+#[should_panic]
+fn out_of_bounds() {
+    Proof::generate(&leaves(3), 3);
+}
@@ -0,0 +1,53 @@
+//! The test files aren't truncated while they're being hashed, so the
+//! `unsafe` calls to `hash_file_mmap()` are fine.
+
+mod common;
+use common::test_data;
+
+#[test]
+fn hash_file_mmap() {
+    let path = std::env::temp_dir().join(format!("tenthash_mmap_test_{}", std::process::id()));
+
+    // Lengths around the block size, and a few larger ones.
+    for len in [
+        0,
+        1,
+        31,
+        32,
+        33,
+        100_000,
+        1 << 20,
+        (1 << 20) + 1,
+        (3 << 20) + 17,
+    ] {
+        let data = test_data(len);
+        std::fs::write(&path, &data).unwrap();
+
+        let digest = unsafe { tenthash::hash_file_mmap(&path) };
+        assert_eq!(digest.unwrap(), tenthash::hash(&data), "length {}", len);
+        assert_eq!(tenthash::hash_file(&path).unwrap(), tenthash::hash(&data));
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn hash_file_mmap_missing() {
+    let err = unsafe { tenthash::hash_file_mmap("this/file/does/not/exist") }.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn hash_file_mmap_directory() {
+    assert!(unsafe { tenthash::hash_file_mmap(std::env::temp_dir()) }.is_err());
+}
+
+/// Special files are hashed with buffered reading.
+#[cfg(unix)]
+#[test]
+fn hash_file_mmap_special_file() {
+    assert_eq!(
+        unsafe { tenthash::hash_file_mmap("/dev/null") }.unwrap(),
+        tenthash::hash([])
+    );
+}
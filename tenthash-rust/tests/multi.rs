@@ -0,0 +1,70 @@
+mod common;
+use common::seeded_test_data;
+
+/// Message lengths that exercise the block boundaries.
+const LENGTHS: &[usize] = &[0, 1, 10, 31, 32, 33, 63, 64, 65, 100, 1000];
+
+#[test]
+fn hash_4x_matches_hash() {
+    for &a in LENGTHS {
+        for &b in LENGTHS {
+            let messages = [
+                seeded_test_data(a, 0),
+                seeded_test_data(b, 1),
+                seeded_test_data(a, 2),
+                seeded_test_data(a.max(b), 3),
+            ];
+            let digests = tenthash::hash_4x(messages.clone());
+            for (message, digest) in messages.iter().zip(digests.iter()) {
+                assert_eq!(*digest, tenthash::hash(message));
+            }
+        }
+    }
+}
+
+#[test]
+fn hash_8x_matches_hash() {
+    for &len in LENGTHS {
+        let messages: [Vec<u8>; 8] = std::array::from_fn(|i| seeded_test_data(len + i * 5, i));
+        let digests = tenthash::hash_8x(messages.clone());
+        for (message, digest) in messages.iter().zip(digests.iter()) {
+            assert_eq!(*digest, tenthash::hash(message));
+        }
+    }
+}
+
+#[test]
+fn hash_many_into_matches_hash() {
+    for count in [0, 1, 7, 8, 9, 17, 100] {
+        let messages: Vec<Vec<u8>> = (0..count)
+            .map(|i| seeded_test_data(LENGTHS[i % LENGTHS.len()], i))
+            .collect();
+        let mut digests = vec![[0u8; 20]; count];
+        tenthash::hash_many_into(&messages, &mut digests);
+        for (message, digest) in messages.iter().zip(digests.iter()) {
+            assert_eq!(*digest, tenthash::hash(message));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn hash_many_into_length_mismatch() {
+    let mut digests = [[0u8; 20]; 2];
+    tenthash::hash_many_into(&[b"a", b"b", b"c"], &mut digests);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_many_matches_hash() {
+    for count in [0, 1, 7, 8, 9, 17, 100] {
+        let messages: Vec<Vec<u8>> = (0..count)
+            .map(|i| seeded_test_data(LENGTHS[(i * 5) % LENGTHS.len()], i))
+            .collect();
+        let digests = tenthash::hash_many(&messages);
+        assert_eq!(digests.len(), count);
+        for (message, digest) in messages.iter().zip(digests.iter()) {
+            assert_eq!(*digest, tenthash::hash(message));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use tenthash::{
+    encoding::DecodeError,
+    multihash::{Multihash, MultihashError, MAX_ENCODED_LEN},
+    Digest, DigestExt,
+};
+
+mod common;
+use common::{fox, FOX_HEX};
+
+/// `CODE` as an unsigned varint.
+const CODE_VARINT: &[u8] = &[0xa0, 0x81, 0xc0, 0x01];
+
+fn encode(mh: &Multihash) -> Vec<u8> {
+    mh.encode(&mut [0; MAX_ENCODED_LEN]).to_vec()
+}
+
+#[test]
+fn new() {
+    let mh = Multihash::new(fox());
+    assert_eq!(mh.digest(), &fox()[..]);
+    assert_eq!(mh.bits(), 160);
+    assert_eq!(Multihash::from(fox()), mh);
+    assert_eq!(Multihash::from(Digest::from(fox())), mh);
+
+    let mh = Multihash::new_128(fox());
+    assert_eq!(mh.digest(), &fox().to_16_bytes()[..]);
+    assert_eq!(mh.bits(), 128);
+    assert_eq!(Multihash::new_128(Digest::from(fox())), mh);
+
+    assert_ne!(Multihash::new(fox()), Multihash::new_128(fox()));
+}
+
+#[test]
+fn binary() {
+    let mh = Multihash::new(fox());
+    let bytes = encode(&mh);
+    assert_eq!(bytes, [CODE_VARINT, &[20], &fox()[..]].concat());
+    assert_eq!(bytes.len(), MAX_ENCODED_LEN);
+    assert_eq!(Multihash::decode(&bytes), Ok(mh));
+
+    let mh = Multihash::new_128(fox());
+    let bytes = encode(&mh);
+    assert_eq!(bytes, [CODE_VARINT, &[16], &fox()[..16]].concat());
+    assert_eq!(Multihash::decode(&bytes), Ok(mh));
+}
+
+#[test]
+fn binary_errors() {
+    let bytes = encode(&Multihash::new(fox()));
+
+    assert_eq!(Multihash::decode(&[]), Err(MultihashError::InvalidVarint));
+    assert_eq!(
+        Multihash::decode(&bytes[..2]),
+        Err(MultihashError::InvalidVarint)
+    );
+    assert_eq!(
+        Multihash::decode(&bytes[..4]),
+        Err(MultihashError::InvalidVarint)
+    );
+    assert_eq!(
+        Multihash::decode(&bytes[..24]),
+        Err(MultihashError::InvalidLength(24))
+    );
+    assert_eq!(
+        Multihash::decode(&[&bytes[..], &[0]].concat()),
+        Err(MultihashError::InvalidLength(26))
+    );
+
+    // SHA-256.
+    assert_eq!(
+        Multihash::decode(&[&[0x12, 20], &fox()[..]].concat()),
+        Err(MultihashError::UnknownCode(0x12))
+    );
+
+    for len in [0, 15, 17, 19, 21, 32] {
+        assert_eq!(
+            Multihash::decode(&[CODE_VARINT, &[len], &[0; 32][..len as usize]].concat()),
+            Err(MultihashError::UnsupportedDigestLength(len as u64))
+        );
+    }
+
+    // Non-minimal varints.
+    assert_eq!(
+        Multihash::decode(&[CODE_VARINT, &[0x94, 0x00], &fox()[..]].concat()),
+        Err(MultihashError::InvalidVarint)
+    );
+    // Too-long varints.
+    assert_eq!(
+        Multihash::decode(&[0xff; 10]),
+        Err(MultihashError::InvalidVarint)
+    );
+}
+
+#[test]
+fn text() {
+    let mh = Multihash::new(fox());
+    let text = format!("tenthash:{}", FOX_HEX);
+    assert_eq!(mh.to_string(), text);
+    assert_eq!(format!("{:?}", mh), format!("Multihash({})", text));
+    assert_eq!(text.parse::<Multihash>(), Ok(mh));
+    assert_eq!(
+        format!("tenthash:{}", FOX_HEX.to_uppercase()).parse::<Multihash>(),
+        Ok(mh)
+    );
+
+    let mh = Multihash::new_128(fox());
+    let text = format!("tenthash:{}", &FOX_HEX[..32]);
+    assert_eq!(mh.to_string(), text);
+    assert_eq!(text.parse::<Multihash>(), Ok(mh));
+}
+
+#[test]
+fn text_errors() {
+    assert_eq!(
+        FOX_HEX.parse::<Multihash>(),
+        Err(MultihashError::UnknownPrefix)
+    );
+    assert_eq!(
+        format!("TentHash:{}", FOX_HEX).parse::<Multihash>(),
+        Err(MultihashError::UnknownPrefix)
+    );
+    assert_eq!(
+        format!("sha1:{}", FOX_HEX).parse::<Multihash>(),
+        Err(MultihashError::UnknownPrefix)
+    );
+    assert_eq!(
+        format!("tenthash:{}", &FOX_HEX[..38]).parse::<Multihash>(),
+        Err(MultihashError::InvalidHex(DecodeError::InvalidLength(38)))
+    );
+    assert_eq!(
+        format!("tenthash: {}", &FOX_HEX[..39]).parse::<Multihash>(),
+        Err(MultihashError::InvalidHex(DecodeError::InvalidCharacter(0)))
+    );
+    assert_eq!(
+        format!("tenthash:{}z", &FOX_HEX[..31]).parse::<Multihash>(),
+        Err(MultihashError::InvalidHex(DecodeError::InvalidCharacter(
+            31
+        )))
+    );
+}
@@ -0,0 +1,150 @@
+use tenthash::{Digest, TentHash};
+
+fn seeded_hash(seed: u64, data: &[u8]) -> Digest {
+    let mut hasher = TentHash::with_seed(seed);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn seeded_hash_bytes(seed: [u8; 32], data: &[u8]) -> Digest {
+    let mut hasher = TentHash::with_seed_bytes(seed);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// The 256-bit seed used in the test vectors: the bytes 0 through 31.
+const BYTES_SEED: [u8; 32] = {
+    let mut seed = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        seed[i] = i as u8;
+        i += 1;
+    }
+    seed
+};
+
+const U64_SEED_VECTORS: &[(u64, &str, &str)] = &[
+    (0, "", "7d305d3cd23e3528db8ae30bb70488a06767a680"),
+    (0, "0123456789", "a718c100c0e83f6b43131254d4a7ea367f4c6023"),
+    (
+        0,
+        "This string is exactly 32 bytes.",
+        "fbe17d9cc81accf5166a59b8998c652f4ce26537",
+    ),
+    (
+        0,
+        "The quick brown fox jumps over the lazy dog.",
+        "e4faf6e215c3b7535a5175bef6811f67c27806b8",
+    ),
+    (1, "", "0e05ea44bf8780239841cda7682f626bed05f576"),
+    (1, "0123456789", "a71a556ee3637fd3c0770c8537f536e125f678fe"),
+    (
+        1,
+        "This string is exactly 32 bytes.",
+        "45456173a97e030fe5ee0f1a2f4518e7ba8a7abf",
+    ),
+    (
+        1,
+        "The quick brown fox jumps over the lazy dog.",
+        "cc206e35bd614f60ba41dc82c57f49bcde6c5c71",
+    ),
+    (
+        0x0123456789abcdef,
+        "",
+        "1365101f76fdd3b1dbc9e9da27b201c0b8a5ddad",
+    ),
+    (
+        0x0123456789abcdef,
+        "0123456789",
+        "501999ff6fabb87c7e1cc1d4d39c8bd7ba8f6395",
+    ),
+    (
+        0x0123456789abcdef,
+        "This string is exactly 32 bytes.",
+        "d5e0bad8394525028ab3124aa2132455417c6ecf",
+    ),
+    (
+        0x0123456789abcdef,
+        "The quick brown fox jumps over the lazy dog.",
+        "0beb2c52e34b09fb8c0dcb289fcc9fa73b70fb5e",
+    ),
+];
+
+/// Test vectors using `BYTES_SEED`.
+const BYTES_SEED_VECTORS: &[(&str, &str)] = &[
+    ("", "9fc88ab2e32010d57b1c7cf3c2cf3e15541dd115"),
+    ("0123456789", "42508d71a8298a26e8e4a78a3bdffd8cd1c6209a"),
+    (
+        "This string is exactly 32 bytes.",
+        "3bf4322d95c85ddb2c4016cd286c09626b50c743",
+    ),
+    (
+        "The quick brown fox jumps over the lazy dog.",
+        "f337671966d041a236b496a4a20b4f27a4b819a6",
+    ),
+];
+
+#[test]
+fn test_vectors() {
+    for &(seed, message, digest) in U64_SEED_VECTORS.iter() {
+        assert_eq!(seeded_hash(seed, message.as_bytes()).to_string(), digest);
+    }
+    for &(message, digest) in BYTES_SEED_VECTORS.iter() {
+        assert_eq!(
+            seeded_hash_bytes(BYTES_SEED, message.as_bytes()).to_string(),
+            digest
+        );
+    }
+}
+
+/// `with_seed()` is documented in terms of `with_seed_bytes()`.
+#[test]
+fn u64_seed_matches_bytes_seed() {
+    let mut u64_seed = [0u8; 32];
+    u64_seed[..8].copy_from_slice(&0x0123456789abcdefu64.to_le_bytes());
+    assert_eq!(
+        seeded_hash(0x0123456789abcdef, b"0123456789"),
+        seeded_hash_bytes(u64_seed, b"0123456789")
+    );
+}
+
+/// The seed doesn't count towards the message length, so seeding must not be
+/// the same as prepending the seed to the data.
+#[test]
+fn not_equivalent_to_prepending_seed() {
+    for &(message, _) in BYTES_SEED_VECTORS.iter() {
+        let prepended = [&BYTES_SEED[..], message.as_bytes()].concat();
+        assert_ne!(
+            seeded_hash_bytes(BYTES_SEED, message.as_bytes()),
+            tenthash::hash(prepended)
+        );
+    }
+}
+
+#[test]
+fn split_updates() {
+    let data: Vec<u8> = (0..100).map(|i| i as u8).collect();
+    let expected = seeded_hash(7, &data);
+
+    for split in [0, 1, 31, 32, 33, 64, 99, 100] {
+        let mut hasher = TentHash::with_seed(7);
+        hasher.update(&data[..split]);
+        hasher.update(&data[split..]);
+        assert_eq!(Digest::from(hasher.finalize()), expected, "split {}", split);
+    }
+}
+
+#[test]
+fn seeds_give_different_digests() {
+    let unseeded: Digest = tenthash::hash("0123456789").into();
+    let seeded: Vec<Digest> = (0..100)
+        .map(|seed| seeded_hash(seed, b"0123456789"))
+        .collect();
+
+    for (i, a) in seeded.iter().enumerate() {
+        assert_ne!(*a, unseeded);
+        for b in seeded[(i + 1)..].iter() {
+            assert_ne!(a, b);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use tenthash::{multihash::Multihash, Digest, TentHash};
+
+mod common;
+use common::{fox, to_hex, FOX, FOX_HEX};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    digest: Digest,
+    #[serde(with = "tenthash::serde_digest")]
+    raw_digest: [u8; 20],
+    multihash: Multihash,
+}
+
+fn entry() -> Entry {
+    Entry {
+        digest: Digest::from(fox()),
+        raw_digest: fox(),
+        multihash: Multihash::new_128(fox()),
+    }
+}
+
+#[test]
+fn json() {
+    let json = serde_json::to_string(&entry()).unwrap();
+    assert_eq!(
+        json,
+        format!(
+            r#"{{"digest":"{0}","raw_digest":"{0}","multihash":"tenthash:{1}"}}"#,
+            FOX_HEX,
+            &FOX_HEX[..32]
+        )
+    );
+    assert_eq!(serde_json::from_str::<Entry>(&json).unwrap(), entry());
+
+    // Uppercase hex is accepted too.
+    let upper = format!(r#""{}""#, FOX_HEX.to_uppercase());
+    assert_eq!(
+        serde_json::from_str::<Digest>(&upper).unwrap(),
+        Digest::from(fox())
+    );
+}
+
+#[test]
+fn json_errors() {
+    assert!(serde_json::from_str::<Digest>(&format!(r#""{}""#, &FOX_HEX[..38])).is_err());
+    assert!(serde_json::from_str::<Digest>(r#""not hex""#).is_err());
+    assert!(serde_json::from_str::<Multihash>(&format!(r#""{}""#, FOX_HEX)).is_err());
+    assert!(serde_json::from_str::<Digest>("[1, 2, 3]").is_err());
+}
+
+#[test]
+fn messagepack() {
+    let bytes = rmp_serde::to_vec(&Digest::from(fox())).unwrap();
+    // bin8 marker, length, and the raw digest.
+    assert_eq!(bytes, [&[0xc4, 20], &fox()[..]].concat());
+
+    let bytes = rmp_serde::to_vec(&entry()).unwrap();
+    assert_eq!(rmp_serde::from_slice::<Entry>(&bytes).unwrap(), entry());
+
+    // Truncated digests are rejected.
+    let bytes = rmp_serde::to_vec(&Bytes(&fox()[..19])).unwrap();
+    assert!(rmp_serde::from_slice::<Digest>(&bytes).is_err());
+}
+
+#[test]
+fn bincode() {
+    let bytes = bincode::serialize(&Digest::from(fox())).unwrap();
+    // Length prefix and the raw digest.
+    assert_eq!(bytes, [&20u64.to_le_bytes()[..], &fox()[..]].concat());
+
+    let bytes = bincode::serialize(&entry()).unwrap();
+    assert_eq!(bincode::deserialize::<Entry>(&bytes).unwrap(), entry());
+}
+
+#[test]
+fn state_round_trip() {
+    let data: Vec<u8> = (0..100).map(|i| i as u8).collect();
+
+    for split in [0, 1, 31, 32, 33, 45, 64, 100] {
+        let mut hasher = TentHash::new();
+        hasher.update(&data[..split]);
+
+        let json = serde_json::to_string(&hasher).unwrap();
+        let from_json: TentHash = serde_json::from_str(&json).unwrap();
+
+        let msgpack = rmp_serde::to_vec(&hasher).unwrap();
+        let from_msgpack: TentHash = rmp_serde::from_slice(&msgpack).unwrap();
+
+        let bincode = bincode::serialize(&hasher).unwrap();
+        let from_bincode: TentHash = bincode::deserialize(&bincode).unwrap();
+
+        for mut resumed in [from_json, from_msgpack, from_bincode] {
+            assert_eq!(resumed.export_state(), hasher.export_state());
+            resumed.update(&data[split..]);
+            assert_eq!(resumed.finalize(), tenthash::hash(&data), "split {}", split);
+        }
+    }
+}
+
+#[test]
+fn state_json_is_hex() {
+    let mut hasher = TentHash::new();
+    hasher.update(FOX);
+
+    let json = serde_json::to_string(&hasher).unwrap();
+    assert_eq!(json, format!(r#""{}""#, to_hex(hasher.export_state())));
+
+    // Uppercase hex is accepted too.
+    let upper: TentHash = serde_json::from_str(&json.to_uppercase()).unwrap();
+    assert_eq!(upper.export_state(), hasher.export_state());
+
+    // Wrong lengths, non-hex text, and invalid states are rejected.
+    assert!(serde_json::from_str::<TentHash>(&json[..(json.len() - 3)]).is_err());
+    assert!(serde_json::from_str::<TentHash>(r#""not hex""#).is_err());
+    let invalid = format!(r#""ff{}""#, &json[3..]);
+    assert!(serde_json::from_str::<TentHash>(&invalid).is_err());
+}
+
+/// A byte slice that serializes as bytes rather than as a sequence.
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
@@ -0,0 +1,128 @@
+use tenthash::{StateError, TentHash};
+
+mod common;
+use common::test_data;
+
+#[test]
+fn round_trip_resumes_hashing() {
+    let data = test_data(1000);
+
+    for split in [0, 1, 31, 32, 33, 64, 100, 999, 1000] {
+        let mut hasher = TentHash::new();
+        hasher.update(&data[..split]);
+        let exported = hasher.export_state();
+
+        let mut resumed = TentHash::import_state(&exported).unwrap();
+        assert_eq!(resumed.export_state(), exported);
+
+        resumed.update(&data[split..]);
+        assert_eq!(resumed.finalize(), tenthash::hash(&data));
+    }
+}
+
+/// Guards against accidental changes to the exported state format.
+#[test]
+fn exported_layout() {
+    let mut hasher = TentHash::new();
+    hasher.update(test_data(35));
+    let exported = hasher.export_state();
+
+    assert_eq!(exported.len(), TentHash::EXPORTED_STATE_SIZE);
+    assert_eq!(exported.len(), 74);
+    assert_eq!(exported[0], 1);
+    assert_eq!(&exported[33..41], &35u64.to_le_bytes());
+    assert_eq!(exported[41], 3);
+    assert_eq!(&exported[42..45], &[32, 33, 34]);
+    assert!(exported[45..].iter().all(|&b| b == 0));
+
+    // The initial state is stored verbatim.
+    let exported = TentHash::new().export_state();
+    assert_eq!(&exported[1..9], &0x5d6daffc4411a967u64.to_le_bytes());
+    assert_eq!(&exported[25..33], &0x894e29b9611eb173u64.to_le_bytes());
+}
+
+#[test]
+fn import_errors() {
+    let mut hasher = TentHash::new();
+    hasher.update(test_data(35));
+    let valid = hasher.export_state();
+
+    assert_eq!(
+        TentHash::import_state(&valid[..73]).unwrap_err(),
+        StateError::InvalidLength(73)
+    );
+    assert_eq!(
+        TentHash::import_state(&[]).unwrap_err(),
+        StateError::InvalidLength(0)
+    );
+
+    let mut bad = valid;
+    bad[0] = 2;
+    assert_eq!(
+        TentHash::import_state(&bad).unwrap_err(),
+        StateError::UnsupportedVersion(2)
+    );
+
+    let mut bad = valid;
+    bad[41] = 33;
+    assert_eq!(
+        TentHash::import_state(&bad).unwrap_err(),
+        StateError::InvalidBufferLength(33)
+    );
+
+    let mut bad = valid;
+    bad[41] = 4;
+    assert_eq!(
+        TentHash::import_state(&bad).unwrap_err(),
+        StateError::InconsistentMessageLength
+    );
+
+    let mut bad = valid;
+    bad[33..41].copy_from_slice(&2u64.to_le_bytes());
+    assert_eq!(
+        TentHash::import_state(&bad).unwrap_err(),
+        StateError::InconsistentMessageLength
+    );
+
+    let mut bad = valid;
+    bad[73] = 1;
+    assert_eq!(
+        TentHash::import_state(&bad).unwrap_err(),
+        StateError::NonZeroPadding
+    );
+}
+
+/// A full but not yet processed block buffer is a valid state.
+#[test]
+fn import_full_buffer() {
+    let data = test_data(64);
+    let mut hasher = TentHash::new();
+    hasher.update(&data[..10]);
+    hasher.update(&data[10..32]);
+    let exported = hasher.export_state();
+    assert_eq!(exported[41], 32);
+
+    let mut resumed = TentHash::import_state(&exported).unwrap();
+    resumed.update(&data[32..]);
+    assert_eq!(resumed.finalize(), tenthash::hash(&data));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let data = test_data(100);
+    let mut hasher = TentHash::new();
+    hasher.update(&data[..45]);
+
+    let json = serde_json::to_string(&hasher).unwrap();
+    let mut resumed: TentHash = serde_json::from_str(&json).unwrap();
+    assert_eq!(resumed.export_state(), hasher.export_state());
+
+    resumed.update(&data[45..]);
+    assert_eq!(resumed.finalize(), tenthash::hash(&data));
+
+    // Invalid states are rejected.
+    assert!(serde_json::from_str::<TentHash>("[1, 2, 3]").is_err());
+    let too_long = format!("[{}]", vec!["0"; 75].join(","));
+    assert!(serde_json::from_str::<TentHash>(&too_long).is_err());
+}
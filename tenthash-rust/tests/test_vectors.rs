@@ -1,5 +1,8 @@
 use tenthash::DigestExt;
-use tenthash::TentHash;
+use tenthash::{Digest, TentHash};
+
+mod common;
+use common::{test_data, to_hex};
 
 const TEST_VECTORS: &[(&[u8], &str)] = &[
     (&[], "68c8213b7a76b8ed267dddb3d8717bb3b6e7cc0a"),
@@ -14,42 +17,10 @@ const TEST_VECTORS: &[(&[u8], &str)] = &[
     ),
 ];
 
-/// Returns a printable hex string version of the digest.
-pub fn digest_to_string(digest: &[u8]) -> String {
-    fn low_bits_to_char(n: u8) -> char {
-        match n {
-            0 => '0',
-            1 => '1',
-            2 => '2',
-            3 => '3',
-            4 => '4',
-            5 => '5',
-            6 => '6',
-            7 => '7',
-            8 => '8',
-            9 => '9',
-            10 => 'a',
-            11 => 'b',
-            12 => 'c',
-            13 => 'd',
-            14 => 'e',
-            15 => 'f',
-            _ => unreachable!(),
-        }
-    }
-
-    let mut s = String::new();
-    for byte in digest.iter() {
-        s.push(low_bits_to_char(byte >> 4u8));
-        s.push(low_bits_to_char(byte & 0b00001111));
-    }
-    s
-}
-
 #[test]
 fn single_call() {
     for (data, digest) in TEST_VECTORS.iter().copied() {
-        assert_eq!(digest_to_string(&tenthash::hash(data)), digest);
+        assert_eq!(Digest::from(tenthash::hash(data)).to_string(), digest);
     }
 }
 
@@ -58,20 +29,20 @@ fn streaming_one_chunk() {
     for (data, digest) in TEST_VECTORS.iter().copied() {
         let mut hasher = TentHash::new();
         hasher.update(data);
-        assert_eq!(digest_to_string(&hasher.finalize()), digest);
+        assert_eq!(Digest::from(hasher.finalize()).to_string(), digest);
     }
 }
 
 #[test]
 fn streaming_multi_chunk() {
     for chunk_size in 1..1024 {
-        for (data, digest) in TEST_VECTORS.iter().copied() {
+        for &(data, digest) in TEST_VECTORS {
             if data.len() >= chunk_size {
                 let mut hasher = TentHash::new();
                 for chunk in data.chunks(chunk_size) {
                     hasher.update(chunk);
                 }
-                assert_eq!(digest_to_string(&hasher.finalize()), digest);
+                assert_eq!(Digest::from(hasher.finalize()).to_string(), digest);
             }
         }
     }
@@ -82,8 +53,8 @@ fn full_vs_128_digest() {
     for (data, digest_full) in TEST_VECTORS.iter().copied() {
         let digest = tenthash::hash(data);
 
-        let digest_16_bytes = digest_to_string(&digest.to_16_bytes());
-        let digest_128 = digest_to_string(&digest.to_u128().to_le_bytes());
+        let digest_16_bytes = to_hex(digest.to_16_bytes());
+        let digest_128 = to_hex(digest.to_u128().to_le_bytes());
 
         assert_eq!(32, digest_16_bytes.len());
         assert_eq!(32, digest_128.len());
@@ -91,3 +62,77 @@ fn full_vs_128_digest() {
         assert!(digest_full.starts_with(&digest_128));
     }
 }
+
+//-------------------------------------------------------------
+// The shared test vector corpus in `test_vectors/corpus.txt`.
+
+/// The path of the corpus, relative to this crate.
+const CORPUS_PATH: &str = "../test_vectors/corpus.txt";
+
+/// Reads and parses the corpus, returning `(message, digest)` pairs.
+fn corpus() -> Vec<(Vec<u8>, String)> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(CORPUS_PATH);
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("couldn't read {}: {}", path.display(), e));
+
+    let mut lines = text
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    assert_eq!(lines.next(), Some("tenthash-test-vectors 1"));
+
+    lines
+        .map(|line| {
+            let (digest, message) = line.split_once(' ').unwrap();
+            let data = if let Some(hex) = message.strip_prefix("hex:") {
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..(i + 2)], 16).unwrap())
+                    .collect()
+            } else if let Some(len) = message.strip_prefix("pattern:") {
+                test_data(len.parse().unwrap())
+            } else {
+                panic!("unknown message in corpus: {}", message);
+            };
+            assert_eq!(digest.len(), 40);
+            (data, digest.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn corpus_includes_test_vectors() {
+    let corpus = corpus();
+    for &(data, digest) in TEST_VECTORS.iter() {
+        assert!(corpus.iter().any(|(d, dig)| d == data && dig == digest));
+    }
+}
+
+#[test]
+fn corpus_single_call() {
+    for (data, digest) in corpus() {
+        assert_eq!(
+            Digest::from(tenthash::hash(&data)).to_string(),
+            digest,
+            "{} bytes",
+            data.len()
+        );
+    }
+}
+
+#[test]
+fn corpus_streaming() {
+    // Odd chunk sizes, so that the updates are misaligned with the blocks.
+    for (data, digest) in corpus() {
+        let chunk_size = (data.len() / 7).max(1) | 1;
+        let mut hasher = TentHash::new();
+        for chunk in data.chunks(chunk_size) {
+            hasher.update(chunk);
+        }
+        assert_eq!(
+            Digest::from(hasher.finalize()).to_string(),
+            digest,
+            "{} bytes",
+            data.len()
+        );
+    }
+}
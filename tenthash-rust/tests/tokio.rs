@@ -0,0 +1,44 @@
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use tenthash::{HashingReader, HashingWriter};
+
+mod common;
+use common::test_data;
+
+#[tokio::test]
+async fn reader() {
+    let data = test_data(100_000);
+    let mut reader = HashingReader::new(&data[..]);
+
+    let mut contents = Vec::new();
+    reader.read_to_end(&mut contents).await.unwrap();
+
+    assert_eq!(contents, data);
+    assert_eq!(reader.digest(), tenthash::hash(&data));
+}
+
+#[tokio::test]
+async fn writer() {
+    let data = test_data(100_000);
+    let mut writer = HashingWriter::new(Vec::new());
+
+    for chunk in data.chunks(1000) {
+        writer.write_all(chunk).await.unwrap();
+    }
+    writer.shutdown().await.unwrap();
+
+    assert_eq!(writer.digest(), tenthash::hash(&data));
+    assert_eq!(writer.into_inner(), data);
+}
+
+#[tokio::test]
+async fn copy() {
+    let data = test_data(100_000);
+    let mut reader = HashingReader::new(&data[..]);
+    let mut writer = HashingWriter::new(Vec::new());
+
+    tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+
+    assert_eq!(reader.digest(), tenthash::hash(&data));
+    assert_eq!(writer.digest(), tenthash::hash(&data));
+}
@@ -0,0 +1,134 @@
+use tenthash::tree::{self, TreeDigest, TreeHash, CHUNK_SIZE};
+
+mod common;
+use common::{test_data, to_hex};
+
+/// A straightforward recursive implementation of the spec, built on plain
+/// TentHash.
+fn reference_tree_hash(data: &[u8]) -> [u8; 20] {
+    fn subtree(chunks: &[&[u8]]) -> [u8; 20] {
+        if chunks.len() == 1 {
+            return tenthash::hash([chunks[0], &[0x00]].concat());
+        }
+        let mut k = 1;
+        while k * 2 < chunks.len() {
+            k *= 2;
+        }
+        let left = subtree(&chunks[..k]);
+        let right = subtree(&chunks[k..]);
+        tenthash::hash([&left[..], &right[..], &[0x01]].concat())
+    }
+
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![&[]]
+    } else {
+        data.chunks(CHUNK_SIZE).collect()
+    };
+    tenthash::hash([&subtree(&chunks)[..], &[0x02]].concat())
+}
+
+/// Input lengths that exercise the chunk and tree boundaries.
+const LENGTHS: &[usize] = &[
+    0,
+    1,
+    CHUNK_SIZE - 1,
+    CHUNK_SIZE,
+    CHUNK_SIZE + 1,
+    CHUNK_SIZE * 2,
+    CHUNK_SIZE * 3,
+    CHUNK_SIZE * 4,
+    CHUNK_SIZE * 4 + 1,
+    CHUNK_SIZE * 5 + 1000,
+    CHUNK_SIZE * 8,
+    CHUNK_SIZE * 13 + 7,
+];
+
+/// Test vectors for TentHash Tree.  The input for each vector is
+/// `test_data(len)`.
+const TEST_VECTORS: &[(usize, &str)] = &[
+    (0, "4efcc1db077acf4710e835c9bedb8fc7bb680d73"),
+    (1, "5d5ed5089c06d33a4cb966cb93609ff264d1f44f"),
+    (65535, "da9793d97f7f3224ba1d9c7d9da17200d235381e"),
+    (65536, "de64582179fdc959c613454e26449f469f58ccb3"),
+    (65537, "65a648d38b3082bf3aa9149da9e645db06e85aa9"),
+    (131072, "46615af43d07d187d063cda54f7f49dbad35ce29"),
+    (262145, "75b8d7e3da602f42c55de5248a35d81fe0f8f847"),
+    (852039, "293e8e1a952c8d84678ce24001f9fb90d96372d2"),
+];
+
+#[test]
+fn test_vectors() {
+    for &(len, digest) in TEST_VECTORS.iter() {
+        assert_eq!(to_hex(tree::hash(test_data(len))), digest, "length {}", len);
+    }
+}
+
+#[test]
+fn matches_reference() {
+    for &len in LENGTHS {
+        let data = test_data(len);
+        assert_eq!(
+            tree::hash(&data).to_bytes(),
+            reference_tree_hash(&data),
+            "length {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn streaming() {
+    let data = test_data(CHUNK_SIZE * 5 + 1000);
+    let expected = tree::hash(&data);
+
+    for piece_size in [1, 31, 32, 1000, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1] {
+        let mut hasher = TreeHash::new();
+        for piece in data.chunks(piece_size) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finalize(), expected, "piece size {}", piece_size);
+    }
+
+    // Empty updates don't affect anything.
+    let mut hasher = TreeHash::new();
+    hasher.update(&data[..CHUNK_SIZE]);
+    hasher.update([]);
+    hasher.update(&data[CHUNK_SIZE..]);
+    assert_eq!(hasher.finalize(), expected);
+}
+
+#[test]
+fn differs_from_plain_tenthash() {
+    for &len in LENGTHS {
+        let data = test_data(len);
+        assert_ne!(tree::hash(&data).to_bytes(), tenthash::hash(&data));
+    }
+}
+
+#[test]
+fn digest_formatting() {
+    let digest = tree::hash(test_data(1));
+    let hex = "5d5ed5089c06d33a4cb966cb93609ff264d1f44f";
+
+    assert_eq!(digest.to_string(), hex);
+    assert_eq!(format!("{:X}", digest), hex.to_uppercase());
+    assert_eq!(format!("{:?}", digest), format!("TreeDigest({})", hex));
+    assert_eq!(hex.parse::<TreeDigest>(), Ok(digest));
+    assert_eq!(hex.to_uppercase().parse::<TreeDigest>(), Ok(digest));
+    assert!(hex[1..].parse::<TreeDigest>().is_err());
+    assert_eq!(TreeDigest::from_bytes(digest.to_bytes()), digest);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel() {
+    for &len in LENGTHS {
+        let data = test_data(len);
+        assert_eq!(
+            tree::hash_parallel(&data),
+            tree::hash(&data),
+            "length {}",
+            len
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use tenthash::{multihash::Multihash, verify, Digest, DigestExt, Mismatch, TentHash};
+
+mod common;
+use common::{fox, FOX, FOX_HEX};
+
+const EMPTY_HEX: &str = "68c8213b7a76b8ed267dddb3d8717bb3b6e7cc0a";
+
+#[test]
+fn matching() {
+    let digest = fox();
+    assert_eq!(verify(FOX, &digest), Ok(()));
+    assert_eq!(verify(FOX, &Digest::from(digest)), Ok(()));
+    assert_eq!(verify(FOX, &digest.to_16_bytes()), Ok(()));
+    assert_eq!(verify(FOX, &Multihash::new(digest)), Ok(()));
+    assert_eq!(verify(FOX, &Multihash::new_128(digest)), Ok(()));
+}
+
+#[test]
+fn mismatching() {
+    let err = verify("", &fox()).unwrap_err();
+    assert_eq!(err.expected(), &fox()[..]);
+    assert_eq!(err.actual(), &tenthash::hash("")[..]);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "TentHash digest mismatch: expected {}, got {}",
+            FOX_HEX, EMPTY_HEX
+        )
+    );
+
+    // Truncated digests report truncated digests.
+    let err = verify("", &fox().to_16_bytes()).unwrap_err();
+    assert_eq!(err.expected(), &fox()[..16]);
+    assert_eq!(err.actual(), &tenthash::hash("")[..16]);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "TentHash digest mismatch: expected {}, got {}",
+            &FOX_HEX[..32],
+            &EMPTY_HEX[..32]
+        )
+    );
+
+    // A difference in the last 32 bits only matters for full digests.
+    let mut almost = fox();
+    almost[19] ^= 1;
+    assert!(verify(FOX, &almost).is_err());
+    assert_eq!(verify(FOX, &almost.to_16_bytes()), Ok(()));
+}
+
+#[test]
+fn finalize_verify() {
+    let mut hasher = TentHash::new();
+    hasher.update(&FOX[..10]);
+    hasher.update(&FOX[10..]);
+    assert_eq!(hasher.finalize_verify(fox()), Ok(()));
+    assert_eq!(hasher.finalize_verify(fox().to_16_bytes()), Ok(()));
+
+    let err: Mismatch = TentHash::new().finalize_verify(fox()).unwrap_err();
+    assert_eq!(err.expected(), &fox()[..]);
+    assert_eq!(err.actual(), &tenthash::hash("")[..]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn error_trait() {
+    let err: Box<dyn std::error::Error> = Box::new(verify("", &fox()).unwrap_err());
+    assert!(err.to_string().starts_with("TentHash digest mismatch"));
+}
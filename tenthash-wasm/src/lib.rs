@@ -0,0 +1,84 @@
+//! WebAssembly bindings for the Rust implementation of TentHash.
+//!
+//! This exposes TentHash to JavaScript via
+//! [wasm-bindgen](https://github.com/rustwasm/wasm-bindgen), as a one-shot
+//! `hash()` function and a streaming `TentHash` class.  Digests are returned
+//! either as a 20-byte `Uint8Array` or as a lowercase hex string, matching the
+//! digests computed by every other TentHash implementation.
+//!
+//! ```js
+//! import { hash, hashHex, TentHash } from "tenthash-wasm";
+//!
+//! const digest = hash(new TextEncoder().encode("Hello world!"));
+//!
+//! const hasher = new TentHash();
+//! for await (const chunk of file.stream()) {
+//!     hasher.update(chunk);
+//! }
+//! const hex = hasher.finalizeHex();
+//! ```
+
+use tenthash::Digest;
+use wasm_bindgen::prelude::*;
+
+/// Computes the TentHash digest of `data` in one go, returned as 20 bytes.
+#[wasm_bindgen]
+pub fn hash(data: &[u8]) -> Vec<u8> {
+    tenthash::hash(data).to_vec()
+}
+
+/// Computes the TentHash digest of `data` in one go, returned as 40
+/// lowercase hex digits.
+#[wasm_bindgen(js_name = hashHex)]
+pub fn hash_hex(data: &[u8]) -> String {
+    Digest::from(tenthash::hash(data)).to_string()
+}
+
+/// Computes TentHash incrementally, taking input data in chunks.
+///
+/// The digest is unaffected by how the data is split into chunks, so this can
+/// hash e.g. the chunks of a `ReadableStream` as they arrive, without holding
+/// the whole file in memory.
+#[wasm_bindgen]
+pub struct TentHash {
+    inner: tenthash::TentHash,
+}
+
+#[wasm_bindgen]
+impl TentHash {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> TentHash {
+        TentHash {
+            inner: tenthash::TentHash::new(),
+        }
+    }
+
+    /// Appends `data` to the data being hashed.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Returns the digest of the data hashed so far, as 20 bytes.
+    ///
+    /// The hasher is left unchanged, so more data can still be appended
+    /// afterwards.
+    pub fn finalize(&self) -> Vec<u8> {
+        self.inner.finalize().to_vec()
+    }
+
+    /// Returns the digest of the data hashed so far, as 40 lowercase hex
+    /// digits.
+    ///
+    /// The hasher is left unchanged, so more data can still be appended
+    /// afterwards.
+    #[wasm_bindgen(js_name = finalizeHex)]
+    pub fn finalize_hex(&self) -> String {
+        Digest::from(self.inner.finalize()).to_string()
+    }
+
+    /// Resets the hasher, so that it can be reused for new data.
+    pub fn reset(&mut self) {
+        self.inner = tenthash::TentHash::new();
+    }
+}
@@ -0,0 +1,89 @@
+//! These run under Node.js with `cargo test --target wasm32-unknown-unknown`,
+//! which needs `wasm-bindgen-test-runner` from `wasm-bindgen-cli`.  See the
+//! readme for details.
+
+use tenthash::Digest;
+use tenthash_wasm::{hash, hash_hex, TentHash};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// The shared test vector corpus.  It's embedded rather than read at run
+/// time, because the tests don't have filesystem access under wasm.
+const CORPUS: &str = include_str!("../../test_vectors/corpus.txt");
+
+/// Parses the corpus, returning `(message, digest)` pairs.  See
+/// `test_vectors/README.md` for the format.
+fn corpus() -> Vec<(Vec<u8>, String)> {
+    let mut lines = CORPUS
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    assert_eq!(lines.next(), Some("tenthash-test-vectors 1"));
+
+    lines
+        .map(|line| {
+            let (digest, message) = line.split_once(' ').unwrap();
+            let data = if let Some(hex) = message.strip_prefix("hex:") {
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..(i + 2)], 16).unwrap())
+                    .collect()
+            } else if let Some(len) = message.strip_prefix("pattern:") {
+                let len: usize = len.parse().unwrap();
+                (0..len).map(|i| (i % 251) as u8).collect()
+            } else {
+                panic!("unknown message in corpus: {}", message);
+            };
+            (data, digest.to_string())
+        })
+        .collect()
+}
+
+/// A corpus message that spans several blocks, and its digest.
+fn long_test_vector() -> (Vec<u8>, String) {
+    corpus()
+        .into_iter()
+        .find(|(data, _)| data.len() == 1000)
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+fn one_shot() {
+    for (input, digest) in corpus() {
+        assert_eq!(hash_hex(&input), digest, "{} bytes", input.len());
+        assert_eq!(hash(&input), tenthash::hash(&input));
+    }
+}
+
+#[wasm_bindgen_test]
+fn streaming() {
+    let (data, digest) = long_test_vector();
+
+    for piece_size in [1, 7, 32, 33, 1000] {
+        let mut hasher = TentHash::new();
+        for piece in data.chunks(piece_size) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finalize_hex(), digest, "piece size {}", piece_size);
+        assert_eq!(
+            hasher.finalize(),
+            digest.parse::<Digest>().unwrap().as_bytes()
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn finalize_is_non_destructive() {
+    let mut hasher = TentHash::new();
+    hasher.update(b"Hello");
+    assert_eq!(hasher.finalize(), tenthash::hash("Hello"));
+    hasher.update(b" world!");
+    assert_eq!(hasher.finalize(), tenthash::hash("Hello world!"));
+}
+
+#[wasm_bindgen_test]
+fn reset() {
+    let mut hasher = TentHash::new();
+    hasher.update(b"Hello");
+    hasher.reset();
+    hasher.update(b"Hello world!");
+    assert_eq!(hasher.finalize(), tenthash::hash("Hello world!"));
+}
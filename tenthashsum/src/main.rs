@@ -0,0 +1,512 @@
+//! Computes and checks TentHash digests.
+//!
+//! The command line interface, output format, and exit codes follow the
+//! conventions of the coreutils `*sum` tools (`sha256sum`, `b2sum`, etc.), so
+//! that `tenthashsum` can be used as a drop-in replacement for them.
+
+use std::{
+    borrow::Cow,
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+use tenthash::{encoding::HEX_LEN, Digest, TentHash};
+
+const NAME: &str = "tenthashsum";
+
+/// The algorithm name used in BSD-style (`--tag`) lines.
+const TAG: &str = "TentHash";
+
+const USAGE: &str = "\
+Usage: tenthashsum [OPTION]... [FILE]...
+Print or check TentHash (160-bit) checksums.
+
+With no FILE, or when FILE is -, read standard input.
+
+  -b, --binary          read in binary mode
+  -c, --check           read checksums from the FILEs and check them
+      --tag             create a BSD-style checksum
+  -t, --text            read in text mode (default)
+  -z, --zero            end each output line with NUL, not newline,
+                          and disable file name escaping
+
+The following five options are useful only when verifying checksums:
+      --ignore-missing  don't fail or report status for missing files
+      --quiet           don't print OK for each successfully verified file
+      --status          don't output anything, status code shows success
+      --strict          exit non-zero for improperly formatted checksum lines
+  -w, --warn            warn about improperly formatted checksum lines
+
+      --help            display this help and exit
+      --version         output version information and exit
+
+TentHash is not a cryptographic hash: it detects accidental corruption, but
+not intentional tampering.
+";
+
+#[derive(Debug, Default)]
+struct Options {
+    check: bool,
+    binary: bool,
+    text: bool,
+    tag: bool,
+    zero: bool,
+    ignore_missing: bool,
+    quiet: bool,
+    status: bool,
+    strict: bool,
+    warn: bool,
+    files: Vec<OsString>,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args_os().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}: {}", NAME, message);
+            eprintln!("Try '{} --help' for more information.", NAME);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    let result = if options.check {
+        check_files(&options, &mut out)
+    } else {
+        hash_files(&options, &mut out)
+    };
+
+    match result.and_then(|success| out.flush().map(|_| success)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            if e.kind() != io::ErrorKind::BrokenPipe {
+                eprintln!("{}: write error: {}", NAME, e);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the command line arguments.
+///
+/// Returns `Ok(None)` if the program should exit successfully without doing
+/// anything else (e.g. after printing `--help`).
+fn parse_args(args: impl Iterator<Item = OsString>) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+    let mut only_files = false;
+
+    for arg in args {
+        let arg_str = arg.to_string_lossy();
+        if only_files || arg_str == "-" || !arg_str.starts_with('-') {
+            options.files.push(arg);
+            continue;
+        }
+
+        if let Some(long) = arg_str.strip_prefix("--") {
+            match long {
+                "" => only_files = true,
+                "binary" => options.binary = true,
+                "check" => options.check = true,
+                "tag" => options.tag = true,
+                "text" => options.text = true,
+                "zero" => options.zero = true,
+                "ignore-missing" => options.ignore_missing = true,
+                "quiet" => options.quiet = true,
+                "status" => options.status = true,
+                "strict" => options.strict = true,
+                "warn" => options.warn = true,
+                "help" => {
+                    print!("{}", USAGE);
+                    return Ok(None);
+                }
+                "version" => {
+                    println!("{} {}", NAME, env!("CARGO_PKG_VERSION"));
+                    return Ok(None);
+                }
+                _ => return Err(format!("unrecognized option '{}'", arg_str)),
+            }
+        } else {
+            for flag in arg_str.chars().skip(1) {
+                match flag {
+                    'b' => options.binary = true,
+                    'c' => options.check = true,
+                    't' => options.text = true,
+                    'z' => options.zero = true,
+                    'w' => options.warn = true,
+                    _ => return Err(format!("invalid option -- '{}'", flag)),
+                }
+            }
+        }
+    }
+
+    if options.tag && options.check {
+        return Err("the --tag option is meaningless when verifying checksums".into());
+    }
+    if options.tag && options.text {
+        return Err("--tag does not support --text mode".into());
+    }
+    if options.binary && options.text {
+        return Err("the --binary and --text options are meaningless together".into());
+    }
+    if !options.check {
+        for (is_set, name) in [
+            (options.ignore_missing, "--ignore-missing"),
+            (options.quiet, "--quiet"),
+            (options.status, "--status"),
+            (options.strict, "--strict"),
+            (options.warn, "--warn"),
+        ] {
+            if is_set {
+                return Err(format!(
+                    "the {} option is meaningful only when verifying checksums",
+                    name
+                ));
+            }
+        }
+    }
+
+    if options.files.is_empty() {
+        options.files.push("-".into());
+    }
+
+    Ok(Some(options))
+}
+
+//-------------------------------------------------------------
+// Computing checksums.
+
+/// Hashes each file in `options.files` and prints a checksum line for it.
+///
+/// Returns whether all files were hashed successfully.
+fn hash_files(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let mut success = true;
+
+    for file in options.files.iter() {
+        let digest = match hash_path(file) {
+            Ok(digest) => digest,
+            Err(e) => {
+                out.flush()?;
+                eprintln!("{}: {}: {}", NAME, file.to_string_lossy(), e);
+                success = false;
+                continue;
+            }
+        };
+
+        let name = os_str_to_bytes(file);
+        let escape = !options.zero && needs_escape(&name);
+        if escape {
+            out.write_all(b"\\")?;
+        }
+        if options.tag {
+            write!(out, "{} (", TAG)?;
+            write_name(out, &name, escape)?;
+            write!(out, ") = {}", digest)?;
+        } else {
+            write!(out, "{} {}", digest, if options.binary { '*' } else { ' ' })?;
+            write_name(out, &name, escape)?;
+        }
+        out.write_all(if options.zero { b"\0" } else { b"\n" })?;
+    }
+
+    Ok(success)
+}
+
+/// Hashes the file at `path`, or standard input if `path` is `-`.
+fn hash_path(path: &OsStr) -> io::Result<Digest> {
+    let mut hasher = TentHash::new();
+    if path == "-" {
+        hasher.update_reader(io::stdin().lock())?;
+    } else {
+        hasher.update_reader(File::open(path)?)?;
+    }
+    Ok(hasher.finalize().into())
+}
+
+//-------------------------------------------------------------
+// Checking checksums.
+
+/// Per-checklist tallies, used for the summary warnings.
+#[derive(Debug, Default)]
+struct CheckCounts {
+    properly_formatted: usize,
+    improperly_formatted: usize,
+    verified: usize,
+    mismatched: usize,
+    unreadable: usize,
+}
+
+/// Reads checksum lines from each file in `options.files` and checks them.
+///
+/// Returns whether all checks passed.
+fn check_files(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let mut success = true;
+
+    for checklist in options.files.iter() {
+        let checklist_name = checklist.to_string_lossy();
+        let mut text = Vec::new();
+        let read_result = if checklist == "-" {
+            io::stdin().lock().read_to_end(&mut text)
+        } else {
+            File::open(checklist).and_then(|mut f| f.read_to_end(&mut text))
+        };
+        if let Err(e) = read_result {
+            out.flush()?;
+            eprintln!("{}: {}: {}", NAME, checklist_name, e);
+            success = false;
+            continue;
+        }
+
+        let counts = check_lines(options, &checklist_name, &text, out)?;
+        out.flush()?;
+
+        if counts.properly_formatted == 0 {
+            eprintln!(
+                "{}: {}: no properly formatted checksum lines found",
+                NAME, checklist_name
+            );
+            success = false;
+            continue;
+        }
+
+        if !options.status {
+            warn_count(
+                counts.improperly_formatted,
+                "line is",
+                "lines are",
+                "improperly formatted",
+            );
+            warn_count(
+                counts.unreadable,
+                "listed file",
+                "listed files",
+                "could not be read",
+            );
+            warn_count(
+                counts.mismatched,
+                "computed checksum",
+                "computed checksums",
+                "did NOT match",
+            );
+        }
+
+        if options.ignore_missing && counts.verified == 0 {
+            if !options.status {
+                eprintln!("{}: {}: no file was verified", NAME, checklist_name);
+            }
+            success = false;
+        }
+
+        if counts.mismatched > 0
+            || counts.unreadable > 0
+            || (options.strict && counts.improperly_formatted > 0)
+        {
+            success = false;
+        }
+    }
+
+    Ok(success)
+}
+
+/// Checks all of the checksum lines in `text`, printing the result of each.
+fn check_lines(
+    options: &Options,
+    checklist_name: &str,
+    text: &[u8],
+    out: &mut impl Write,
+) -> io::Result<CheckCounts> {
+    let mut counts = CheckCounts::default();
+
+    let text = text.strip_suffix(b"\n").unwrap_or(text);
+    for (line_index, line) in text.split(|&c| c == b'\n').enumerate() {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.starts_with(b"#") {
+            continue;
+        }
+
+        let (expected, name) = match parse_checksum_line(line) {
+            Some(parsed) => parsed,
+            None => {
+                counts.improperly_formatted += 1;
+                if options.warn {
+                    out.flush()?;
+                    eprintln!(
+                        "{}: {}: {}: improperly formatted {} checksum line",
+                        NAME,
+                        checklist_name,
+                        line_index + 1,
+                        TAG
+                    );
+                }
+                continue;
+            }
+        };
+        counts.properly_formatted += 1;
+
+        let result = match bytes_to_os_string(&name) {
+            Some(path) => hash_path(&path),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "file name is not valid on this platform",
+            )),
+        };
+
+        let status = match result {
+            Ok(digest) if digest == expected => {
+                counts.verified += 1;
+                if options.quiet {
+                    continue;
+                }
+                "OK"
+            }
+            Ok(_) => {
+                counts.mismatched += 1;
+                "FAILED"
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound && options.ignore_missing => {
+                continue;
+            }
+            Err(e) => {
+                counts.unreadable += 1;
+                out.flush()?;
+                eprintln!("{}: {}: {}", NAME, String::from_utf8_lossy(&name), e);
+                "FAILED open or read"
+            }
+        };
+
+        if !options.status {
+            let escape = needs_escape(&name);
+            if escape {
+                out.write_all(b"\\")?;
+            }
+            write_name(out, &name, escape)?;
+            writeln!(out, ": {}", status)?;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Parses a checksum line in either the default or BSD-style format.
+///
+/// Returns the expected digest and the (unescaped) file name.
+fn parse_checksum_line(line: &[u8]) -> Option<(Digest, Vec<u8>)> {
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (hex, name) = if let Some(rest) = line
+        .strip_prefix(TAG.as_bytes())
+        .and_then(|rest| rest.strip_prefix(b" ("))
+    {
+        // BSD style: `TentHash (NAME) = DIGEST`
+        let split = rest.windows(4).rposition(|w| w == b") = ")?;
+        (&rest[(split + 4)..], &rest[..split])
+    } else {
+        // Default style: `DIGEST  NAME` or `DIGEST *NAME`
+        if line.len() < HEX_LEN + 2
+            || line[HEX_LEN] != b' '
+            || !matches!(line[HEX_LEN + 1], b' ' | b'*')
+        {
+            return None;
+        }
+        (&line[..HEX_LEN], &line[(HEX_LEN + 2)..])
+    };
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let digest = std::str::from_utf8(hex).ok()?.parse::<Digest>().ok()?;
+    let name = if escaped {
+        unescape_name(name)?
+    } else {
+        name.to_vec()
+    };
+
+    Some((digest, name))
+}
+
+/// Prints a warning summarizing a count, if it's non-zero.
+fn warn_count(count: usize, singular: &str, plural: &str, what: &str) {
+    if count > 0 {
+        let noun = if count == 1 { singular } else { plural };
+        eprintln!("{}: WARNING: {} {} {}", NAME, count, noun, what);
+    }
+}
+
+//-------------------------------------------------------------
+// File name handling.
+
+/// Whether a file name needs escaping to be printed on a single line.
+fn needs_escape(name: &[u8]) -> bool {
+    name.iter().any(|c| matches!(c, b'\\' | b'\n' | b'\r'))
+}
+
+/// Writes a file name, escaping backslashes and line breaks if requested.
+fn write_name(out: &mut impl Write, name: &[u8], escape: bool) -> io::Result<()> {
+    if !escape {
+        return out.write_all(name);
+    }
+
+    for &c in name {
+        match c {
+            b'\\' => out.write_all(b"\\\\")?,
+            b'\n' => out.write_all(b"\\n")?,
+            b'\r' => out.write_all(b"\\r")?,
+            _ => out.write_all(&[c])?,
+        }
+    }
+    Ok(())
+}
+
+/// The inverse of `write_name()` with escaping.  Returns `None` on invalid
+/// escape sequences.
+fn unescape_name(name: &[u8]) -> Option<Vec<u8>> {
+    let mut unescaped = Vec::with_capacity(name.len());
+    let mut iter = name.iter();
+    while let Some(&c) = iter.next() {
+        if c != b'\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match iter.next()? {
+            b'\\' => unescaped.push(b'\\'),
+            b'n' => unescaped.push(b'\n'),
+            b'r' => unescaped.push(b'\r'),
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
+
+#[cfg(unix)]
+fn os_str_to_bytes(s: &OsStr) -> Cow<'_, [u8]> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(s.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn os_str_to_bytes(s: &OsStr) -> Cow<'_, [u8]> {
+    match s.to_string_lossy() {
+        Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+        Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+    }
+}
+
+#[cfg(unix)]
+fn bytes_to_os_string(bytes: &[u8]) -> Option<OsString> {
+    use std::os::unix::ffi::OsStrExt;
+    Some(OsStr::from_bytes(bytes).to_os_string())
+}
+
+#[cfg(not(unix))]
+fn bytes_to_os_string(bytes: &[u8]) -> Option<OsString> {
+    std::str::from_utf8(bytes).ok().map(OsString::from)
+}
@@ -0,0 +1,253 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+const EMPTY_HEX: &str = "68c8213b7a76b8ed267dddb3d8717bb3b6e7cc0a";
+const FOX_HEX: &str = "de77f1c134228be1b5b25c941d5102f87f3e6d39";
+const FOX: &str = "The quick brown fox jumps over the lazy dog.";
+
+/// A temporary directory that's deleted when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        let path =
+            std::env::temp_dir().join(format!("tenthashsum_test_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    fn write(&self, name: &str, contents: &str) {
+        std::fs::write(self.0.join(name), contents).unwrap();
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn run(dir: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tenthashsum"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn hash_files() {
+    let dir = TempDir::new("hash_files");
+    dir.write("fox.txt", FOX);
+    dir.write("empty", "");
+
+    let output = run(&dir.0, &["fox.txt", "empty"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!("{}  fox.txt\n{}  empty\n", FOX_HEX, EMPTY_HEX)
+    );
+
+    let output = run(&dir.0, &["-b", "fox.txt"], "");
+    assert_eq!(stdout(&output), format!("{} *fox.txt\n", FOX_HEX));
+}
+
+#[test]
+fn hash_stdin() {
+    let dir = TempDir::new("hash_stdin");
+
+    let output = run(&dir.0, &[], FOX);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}  -\n", FOX_HEX));
+
+    let output = run(&dir.0, &["-"], "");
+    assert_eq!(stdout(&output), format!("{}  -\n", EMPTY_HEX));
+}
+
+#[test]
+fn hash_tag_and_zero() {
+    let dir = TempDir::new("hash_tag_and_zero");
+    dir.write("fox.txt", FOX);
+    dir.write("back\\slash", "");
+
+    let output = run(&dir.0, &["--tag", "fox.txt"], "");
+    assert_eq!(
+        stdout(&output),
+        format!("TentHash (fox.txt) = {}\n", FOX_HEX)
+    );
+
+    let output = run(&dir.0, &["-z", "fox.txt", "back\\slash"], "");
+    assert_eq!(
+        stdout(&output),
+        format!("{}  fox.txt\0{}  back\\slash\0", FOX_HEX, EMPTY_HEX)
+    );
+}
+
+#[test]
+fn hash_escaped_name() {
+    let dir = TempDir::new("hash_escaped_name");
+    dir.write("back\\slash", "");
+
+    let output = run(&dir.0, &["back\\slash"], "");
+    assert_eq!(stdout(&output), format!("\\{}  back\\\\slash\n", EMPTY_HEX));
+}
+
+#[test]
+fn hash_missing_file() {
+    let dir = TempDir::new("hash_missing_file");
+    dir.write("fox.txt", FOX);
+
+    let output = run(&dir.0, &["missing", "fox.txt"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), format!("{}  fox.txt\n", FOX_HEX));
+    assert!(stderr(&output).contains("missing"));
+}
+
+#[test]
+fn check_round_trip() {
+    let dir = TempDir::new("check_round_trip");
+    dir.write("fox.txt", FOX);
+    dir.write("empty", "");
+    dir.write("back\\slash", "x");
+
+    for flags in [&[][..], &["--tag"], &["-b"]] {
+        let mut args = flags.to_vec();
+        args.extend_from_slice(&["fox.txt", "empty", "back\\slash"]);
+        let sums = run(&dir.0, &args, "");
+        dir.write("SUMS", stdout(&sums));
+
+        let output = run(&dir.0, &["--check", "SUMS"], "");
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(
+            stdout(&output),
+            "fox.txt: OK\nempty: OK\n\\back\\\\slash: OK\n"
+        );
+    }
+}
+
+#[test]
+fn check_failures() {
+    let dir = TempDir::new("check_failures");
+    dir.write("fox.txt", FOX);
+    dir.write("empty", "not empty");
+    dir.write(
+        "SUMS",
+        &format!(
+            "{}  fox.txt\n{}  empty\n{}  missing\n",
+            FOX_HEX, EMPTY_HEX, EMPTY_HEX
+        ),
+    );
+
+    let output = run(&dir.0, &["-c", "SUMS"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "fox.txt: OK\nempty: FAILED\nmissing: FAILED open or read\n"
+    );
+    assert!(stderr(&output).contains("WARNING: 1 listed file could not be read"));
+    assert!(stderr(&output).contains("WARNING: 1 computed checksum did NOT match"));
+
+    let output = run(&dir.0, &["-c", "--quiet", "SUMS"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "empty: FAILED\nmissing: FAILED open or read\n"
+    );
+
+    let output = run(&dir.0, &["-c", "--status", "SUMS"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn check_ignore_missing() {
+    let dir = TempDir::new("check_ignore_missing");
+    dir.write("fox.txt", FOX);
+    dir.write(
+        "SUMS",
+        &format!("{}  fox.txt\n{}  missing\n", FOX_HEX, EMPTY_HEX),
+    );
+    dir.write("ONLY_MISSING", &format!("{}  missing\n", EMPTY_HEX));
+
+    let output = run(&dir.0, &["-c", "--ignore-missing", "SUMS"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "fox.txt: OK\n");
+
+    let output = run(&dir.0, &["-c", "--ignore-missing", "ONLY_MISSING"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("no file was verified"));
+}
+
+#[test]
+fn check_improperly_formatted() {
+    let dir = TempDir::new("check_improperly_formatted");
+    dir.write("fox.txt", FOX);
+    dir.write(
+        "SUMS",
+        &format!("# A comment.\n{}  fox.txt\nnot a checksum line\n", FOX_HEX),
+    );
+    dir.write("GARBAGE", "garbage\n");
+
+    let output = run(&dir.0, &["-c", "SUMS"], "");
+    assert!(output.status.success());
+    assert!(stderr(&output).contains("WARNING: 1 line is improperly formatted"));
+
+    let output = run(&dir.0, &["-c", "--warn", "SUMS"], "");
+    assert!(stderr(&output).contains("SUMS: 3: improperly formatted TentHash checksum line"));
+
+    let output = run(&dir.0, &["-c", "--strict", "SUMS"], "");
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run(&dir.0, &["-c", "GARBAGE"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("no properly formatted checksum lines found"));
+}
+
+#[test]
+fn check_stdin() {
+    let dir = TempDir::new("check_stdin");
+    dir.write("fox.txt", FOX);
+
+    let output = run(&dir.0, &["-c"], &format!("{}  fox.txt\n", FOX_HEX));
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "fox.txt: OK\n");
+}
+
+#[test]
+fn invalid_options() {
+    let dir = TempDir::new("invalid_options");
+
+    for args in [
+        &["--bogus"][..],
+        &["-x"],
+        &["--quiet"],
+        &["--tag", "--check"],
+        &["--tag", "--text"],
+    ] {
+        let output = run(&dir.0, args, "");
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        assert!(stderr(&output).contains("--help"));
+    }
+}
@@ -0,0 +1,92 @@
+//! Generates the TentHash test vector corpus, `test_vectors/corpus.txt`.
+//!
+//! The digests are computed with the Rust reference implementation in
+//! `reference/rust`, and the corpus is written to stdout.  To regenerate it,
+//! run from this directory:
+//!
+//! ```text
+//! cargo run --release > ../corpus.txt
+//! ```
+//!
+//! See `test_vectors/README.md` for the format.
+
+use std::io::{self, BufWriter, Write};
+
+/// The version of the corpus format.  This only changes if the format
+/// changes in an incompatible way, not when vectors are added.
+const FORMAT_VERSION: u32 = 1;
+
+/// The test vectors from the specification.
+const SPEC_VECTORS: &[&[u8]] = &[
+    b"",
+    &[0],
+    b"0123456789",
+    b"abcdefghijklmnopqrstuvwxyz",
+    b"This string is exactly 32 bytes.",
+    b"The quick brown fox jumps over the lazy dog.",
+    b"Lorem ipsum dolor sit amet, consectetur adipisicing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.",
+];
+
+/// The lengths of the short pattern messages: every length up to this.
+const MAX_SHORT_LEN: usize = 1024;
+
+/// The long pattern messages are `2^n` bytes long (which is always a block
+/// boundary) plus and minus one, for `n` in this range.
+const LONG_LEN_LOG2: std::ops::RangeInclusive<u32> = 11..=24;
+
+/// The maximum length of a line in the corpus, in bytes, excluding the line
+/// terminator.  This is part of the format, so that simple implementations
+/// can read lines into a fixed-size buffer.
+const MAX_LINE_LEN: usize = 1024;
+
+fn main() -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    writeln!(out, "# TentHash test vector corpus.")?;
+    writeln!(out, "#")?;
+    writeln!(
+        out,
+        "# Generated by test_vectors/generator.  See test_vectors/README.md for the format."
+    )?;
+    writeln!(out, "tenthash-test-vectors {}", FORMAT_VERSION)?;
+
+    writeln!(out, "\n# The test vectors from the specification.")?;
+    for message in SPEC_VECTORS {
+        write_vector(&mut out, &format!("hex:{}", to_hex(message)), message)?;
+    }
+
+    writeln!(out, "\n# Every length from 0 to {} bytes.", MAX_SHORT_LEN)?;
+    for len in 0..=MAX_SHORT_LEN {
+        write_vector(&mut out, &format!("pattern:{}", len), &pattern(len))?;
+    }
+
+    writeln!(
+        out,
+        "\n# Long messages, at block boundaries plus and minus one."
+    )?;
+    for n in LONG_LEN_LOG2 {
+        let boundary = 1usize << n;
+        let data = pattern(boundary + 1);
+        for len in [boundary - 1, boundary, boundary + 1] {
+            write_vector(&mut out, &format!("pattern:{}", len), &data[..len])?;
+        }
+    }
+
+    out.flush()
+}
+
+/// Writes one test vector line.
+fn write_vector(out: &mut impl Write, message: &str, data: &[u8]) -> io::Result<()> {
+    let line = format!("{} {}", to_hex(&tenthash_reference::hash(data)), message);
+    assert!(line.len() <= MAX_LINE_LEN, "test vector line too long");
+    writeln!(out, "{}", line)
+}
+
+/// Returns the pattern message of the given length: byte `i` is `i % 251`.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}